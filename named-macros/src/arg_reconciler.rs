@@ -0,0 +1,360 @@
+use crate::attr_parser::Attributes;
+use indexmap::IndexMap;
+use proc_macro2::{Ident, Span};
+use quote::{quote, ToTokens};
+use std::collections::BTreeSet;
+use syn::punctuated::Punctuated;
+use syn::{FnArg, Pat, Token, Type};
+
+/// The most distinct `#[cfg(...)]` conditions which arguments may be gated on.
+/// A set of macros is generated for every combination of them, so this keeps the amount of generated code reasonable.
+const MAX_ARG_CFGS: usize = 4;
+
+#[derive(Clone)]
+pub struct ArgDetails {
+    pub args: Vec<Ident>,
+    pub types: Vec<Type>,
+    pub defaults: IndexMap<String, Option<proc_macro2::TokenStream>>,
+    pub descriptions: IndexMap<String, String>,
+    pub options: Option<OptionsArg>,
+    /// The `#[cfg(...)]` conditions of arguments which only exist under some configurations.
+    pub cfgs: IndexMap<String, proc_macro2::TokenStream>,
+    /// Arguments which don't exist under the configuration these details are for, with the condition which would
+    /// enable them.
+    pub disabled: Vec<(Ident, proc_macro2::TokenStream)>,
+    /// Type and const parameters, which may be given explicitly by name at the call site.
+    pub generics: Vec<GenericParam>,
+    /// The hidden struct with a function for each default, if defaults are evaluated by calling those functions rather
+    /// than being pasted into each call site.
+    pub default_fns: Option<Ident>,
+    /// Whether calls are recorded by `#[named(trace)]`, in which case each argument is passed wrapped in a
+    /// `named::__private::trace::Arg`, which says whether it was given or defaulted.
+    pub traced: bool,
+}
+
+/// A type or const parameter of a function.
+#[derive(Clone)]
+pub struct GenericParam {
+    pub name: Ident,
+    pub is_const: bool,
+    /// Used when the parameter isn't given explicitly - if there's no default, it's left to be inferred.
+    pub default: Option<proc_macro2::TokenStream>,
+}
+
+impl ArgDetails {
+    /// Splits these details into one set for each combination of argument `#[cfg(...)]` conditions, along with an
+    /// attribute which enables only that combination.
+    ///
+    /// If no arguments are gated, there's exactly one combination, which is always enabled.
+    pub fn cfg_variants(self) -> Vec<(proc_macro2::TokenStream, ArgDetails)> {
+        if self.cfgs.is_empty() {
+            return vec![(proc_macro2::TokenStream::new(), self)];
+        }
+        let conditions = self
+            .cfgs
+            .values()
+            .map(|cfg| (cfg.to_string(), cfg.clone()))
+            .collect::<IndexMap<_, _>>();
+        (0..1 << conditions.len())
+            .map(|mask: usize| {
+                let enabled = |condition: &proc_macro2::TokenStream| {
+                    let index = conditions.get_index_of(&condition.to_string()).unwrap();
+                    mask & (1 << index) != 0
+                };
+                let predicates = conditions.values().map(|condition| {
+                    if enabled(condition) {
+                        quote! { #condition }
+                    } else {
+                        quote! { not(#condition) }
+                    }
+                });
+                let gate = quote! { #[cfg(all(#(#predicates),*))] };
+
+                let is_active = |arg: &str| self.cfgs.get(arg).map_or(true, enabled);
+                let (args, types) = self
+                    .args
+                    .iter()
+                    .zip(&self.types)
+                    .filter(|(arg, _ty)| is_active(&arg.to_string()))
+                    .map(|(arg, ty)| (arg.clone(), ty.clone()))
+                    .unzip();
+                let details = ArgDetails {
+                    args,
+                    types,
+                    defaults: filter_keys(&self.defaults, is_active),
+                    descriptions: filter_keys(&self.descriptions, is_active),
+                    options: self
+                        .options
+                        .clone()
+                        .filter(|options| is_active(&options.name.to_string())),
+                    cfgs: filter_keys(&self.cfgs, is_active),
+                    generics: self.generics.clone(),
+                    default_fns: self.default_fns.clone(),
+                    traced: self.traced,
+                    disabled: self
+                        .args
+                        .iter()
+                        .filter(|arg| !is_active(&arg.to_string()))
+                        .map(|arg| (arg.clone(), self.cfgs[&arg.to_string()].clone()))
+                        .collect(),
+                };
+                (gate, details)
+            })
+            .collect()
+    }
+}
+
+fn filter_keys<V: Clone>(
+    map: &IndexMap<String, V>,
+    keep: impl Fn(&str) -> bool,
+) -> IndexMap<String, V> {
+    map.iter()
+        .filter(|(key, _value)| keep(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// An argument whose type is a user-defined options struct, whose fields may be passed as if they were named arguments.
+#[derive(Clone)]
+pub struct OptionsArg {
+    pub name: Ident,
+    pub path: syn::Path,
+}
+
+pub fn reconcile(
+    inputs: &Punctuated<FnArg, Token![,]>,
+    attr: &Attributes,
+) -> syn::Result<ArgDetails> {
+    let args: Result<Vec<_>, _> = inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Receiver(_) => {
+                Err(syn::Error::new_spanned(arg, "`named` does not currently support functions which take `self`."))
+            },
+            FnArg::Typed(pat_type) => {
+                if let Pat::Ident(ident) = pat_type.pat.as_ref() {
+                    Ok((ident.ident.clone(), pat_type.ty.as_ref().clone()))
+                } else {
+                    panic!("Didn't recognise function signature - expected all args to be idents, but found: {:?}", pat_type);
+                }
+            }
+        })
+        .collect();
+    let (args, types): (Vec<_>, Vec<_>) = args?.into_iter().unzip();
+
+    let mut cfgs = IndexMap::new();
+    for (arg, input) in args.iter().zip(inputs) {
+        if let FnArg::Typed(pat_type) = input {
+            let conditions = pat_type
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .map(|attr| attr.parse_args::<proc_macro2::TokenStream>())
+                .collect::<syn::Result<Vec<_>>>()?;
+            match conditions.len() {
+                0 => {}
+                1 => {
+                    cfgs.insert(arg.to_string(), conditions[0].clone());
+                }
+                _ => {
+                    cfgs.insert(arg.to_string(), quote! { all(#(#conditions),*) });
+                }
+            }
+        }
+    }
+    let distinct_cfgs = cfgs
+        .values()
+        .map(|cfg| cfg.to_string())
+        .collect::<BTreeSet<_>>();
+    if distinct_cfgs.len() > MAX_ARG_CFGS {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "Arguments may be gated on at most {} distinct `#[cfg(...)]` conditions, but found {}",
+                MAX_ARG_CFGS,
+                distinct_cfgs.len(),
+            ),
+        ));
+    }
+
+    let defaults = attr.defaults();
+    let docs = attr.docs();
+
+    let options = match attr.options() {
+        Some(path) => Some(find_options_arg(inputs, path)?),
+        None => None,
+    };
+
+    let fn_arg_names = args
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect::<BTreeSet<_>>();
+    let attr_arg_spans = defaults
+        .iter()
+        .map(|(name, (span, _value))| (name, span))
+        .chain(docs.iter().map(|(name, (span, _value))| (name, span)))
+        .collect::<IndexMap<_, _>>();
+    let attr_arg_names = attr_arg_spans
+        .keys()
+        .map(|ident| ident.to_string())
+        .collect::<BTreeSet<_>>();
+    let extras = attr_arg_names
+        .difference(&fn_arg_names)
+        .cloned()
+        .collect::<Vec<_>>();
+    if !extras.is_empty() {
+        let extras_plural_suffix;
+        let span;
+        let extras_str;
+        if extras.len() == 1 {
+            extras_plural_suffix = "";
+            span = *attr_arg_spans[&extras[0]];
+            extras_str = format!("`{}`", &extras[0]);
+        } else {
+            extras_plural_suffix = "s";
+            span = Span::call_site();
+            extras_str = format!("[{}]", extras.join(", "));
+        }
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "Unrecognized argument{} - attribute had argument{} {} but function takes argument{}: [{}]",
+                extras_plural_suffix,
+                extras_plural_suffix,
+                extras_str,
+                if fn_arg_names.len() == 1 { "" } else { "s" },
+                fn_arg_names.into_iter().collect::<Vec<_>>().join(", "),
+            )
+        ));
+    }
+
+    let defaults = args
+        .iter()
+        .map(|arg| {
+            let arg = arg.to_string();
+            let value = match defaults.get(&arg) {
+                Some((_span, value)) => Some(value.to_token_stream()),
+                // An options struct which isn't explicitly defaulted falls back to its Default impl.
+                None => options
+                    .as_ref()
+                    .filter(|options| options.name == arg)
+                    .map(|options| {
+                        let path = &options.path;
+                        quote! { <#path as ::core::default::Default>::default() }
+                    }),
+            };
+            (arg, value)
+        })
+        .collect();
+
+    let descriptions = docs
+        .into_iter()
+        .map(|(name, (_span, value))| (name, value))
+        .collect();
+
+    Ok(ArgDetails {
+        args,
+        types,
+        defaults,
+        descriptions,
+        options,
+        cfgs,
+        disabled: Vec::new(),
+        generics: Vec::new(),
+        default_fns: None,
+        traced: false,
+    })
+}
+
+/// Works out which of a function's generic parameters may be given by name, and their defaults.
+///
+/// Lifetimes are always left to be inferred.
+pub fn reconcile_generics(
+    generics: &syn::Generics,
+    details: &ArgDetails,
+    attr: &Attributes,
+) -> syn::Result<Vec<GenericParam>> {
+    let mut types = attr.types();
+    let params = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(ty) => Some((ty.ident.clone(), false)),
+            syn::GenericParam::Const(c) => Some((c.ident.clone(), true)),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .map(|(name, is_const)| {
+            if details.args.contains(&name) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "`{}` cannot be both an argument and a generic parameter",
+                        name
+                    ),
+                ));
+            }
+            let default = types
+                .shift_remove(&name.to_string())
+                .map(|(_span, value)| value);
+            Ok(GenericParam {
+                name,
+                is_const,
+                default,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    if let Some((extra, (span, _value))) = types.into_iter().next() {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "Unrecognized generic parameter - attribute had type for `{}` but function has generic parameters: [{}]",
+                extra,
+                params
+                    .iter()
+                    .map(|param| param.name.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        ));
+    }
+    Ok(params)
+}
+
+fn find_options_arg(
+    inputs: &Punctuated<FnArg, Token![,]>,
+    path: &syn::Path,
+) -> syn::Result<OptionsArg> {
+    let wanted = path.to_token_stream().to_string();
+    let mut matching = inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(pat_type) => match (pat_type.pat.as_ref(), pat_type.ty.as_ref()) {
+            (Pat::Ident(ident), Type::Path(ty))
+                if ty.qself.is_none() && ty.path.to_token_stream().to_string() == wanted =>
+            {
+                Some(ident.ident.clone())
+            }
+            _ => None,
+        },
+        FnArg::Receiver(_) => None,
+    });
+    match (matching.next(), matching.next()) {
+        (Some(name), None) => Ok(OptionsArg {
+            name,
+            path: path.clone(),
+        }),
+        (None, _) => Err(syn::Error::new_spanned(
+            path,
+            format!(
+                "Unrecognized options type - no argument has type `{}`",
+                wanted
+            ),
+        )),
+        (Some(_), Some(_)) => Err(syn::Error::new_spanned(
+            path,
+            format!(
+                "Ambiguous options type - more than one argument has type `{}`",
+                wanted
+            ),
+        )),
+    }
+}
@@ -0,0 +1,264 @@
+use crate::arg_reconciler::ArgDetails;
+use crate::attr_parser::Attributes;
+use crate::defaults::ElidedLifetimes;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::visit_mut::VisitMut;
+
+/// A struct holding a function's arguments, which is generated for `#[named(serde)]`, `#[named(cli)]` and
+/// `#[named(ffi)]` so that the arguments can be built up from data the function is called with at runtime, and then
+/// passed to it all at once.
+pub struct ArgsStruct<'a> {
+    /// The name of the function's macro.
+    pub macro_name: syn::Ident,
+    pub name: syn::Ident,
+    /// The function's generics, along with a lifetime for any elided lifetimes in its arguments' types, as the struct
+    /// outlives the call.
+    pub generics: syn::Generics,
+    /// The function's return type, with the same lifetimes as the struct.
+    pub output: syn::Type,
+    types: Vec<syn::Type>,
+    f: &'a syn::ItemFn,
+    details: &'a ArgDetails,
+}
+
+impl<'a> ArgsStruct<'a> {
+    pub fn new(name: &syn::Ident, f: &'a syn::ItemFn, details: &'a ArgDetails) -> Self {
+        let mut elided = ElidedLifetimes::new(syn::parse_quote! { '__named });
+        let types = details
+            .types
+            .iter()
+            .map(|ty| {
+                let mut ty = ty.clone();
+                elided.visit_type_mut(&mut ty);
+                ty
+            })
+            .collect();
+        let mut output = match &f.sig.output {
+            syn::ReturnType::Default => syn::parse_quote! { () },
+            syn::ReturnType::Type(_, ty) => ty.as_ref().clone(),
+        };
+        elided.visit_type_mut(&mut output);
+        let mut generics = f.sig.generics.clone();
+        if elided.found {
+            generics.params.insert(0, syn::parse_quote! { '__named });
+        }
+        Self {
+            macro_name: name.clone(),
+            name: args_struct_name(name),
+            generics,
+            output,
+            types,
+            f,
+            details,
+        }
+    }
+
+    /// Generates the struct, with whatever `attr` asks for it to support, along with a `call` method which calls `target`
+    /// with its fields.
+    pub fn generate(&self, target: &syn::Ident, attr: &Attributes) -> TokenStream {
+        let vis = &self.f.vis;
+        let args_name = &self.name;
+        let details = self.details;
+        let fields = details.args.iter().zip(&self.types).map(|(arg, ty)| {
+            let key = arg.to_string();
+            let doc = details
+                .descriptions
+                .get(&key)
+                .map(|description| format!(" {}", description.trim_start()))
+                .map(|description| quote! { #[doc = #description] });
+            let cfg = details.cfgs.get(&key).map(|cfg| quote! { #[cfg(#cfg)] });
+            quote! { #doc #cfg #vis #arg: #ty }
+        });
+
+        let phantoms = self.phantoms();
+        let marker = if phantoms.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                #[doc(hidden)]
+                #vis __named_marker: ::core::marker::PhantomData<fn() -> (#(#phantoms,)*)>,
+            })
+        };
+        let turbofish_params: Vec<_> = self
+            .f
+            .sig
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Type(ty) => Some(&ty.ident),
+                syn::GenericParam::Const(c) => Some(&c.ident),
+                syn::GenericParam::Lifetime(_) => None,
+            })
+            .collect();
+        let turbofish = if turbofish_params.is_empty() {
+            None
+        } else {
+            Some(quote! { ::<#(#turbofish_params),*> })
+        };
+
+        let generics = &self.generics;
+        let output = &self.output;
+        let asyncness = &self.f.sig.asyncness;
+        let unsafety = &self.f.sig.unsafety;
+        let dot_await = self.dot_await();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let calls = details
+            .clone()
+            .cfg_variants()
+            .into_iter()
+            .map(|(gate, details)| {
+                let args = details.args.iter().map(|arg| quote! { self.#arg });
+                let call = self.in_unsafety(quote! { #target #turbofish(#(#args),*) });
+                let call = quote! {
+                    impl #impl_generics #args_name #ty_generics #where_clause {
+                        /// Calls the function with these arguments.
+                        #vis #asyncness #unsafety fn call(self) -> #output {
+                            #call #dot_await
+                        }
+                    }
+                };
+                crate::propagate::gate(&[gate], call)
+            });
+
+        let mut docs = vec![format!(
+            " The arguments of `{}!`, which can be built up at runtime and then passed to it all at once.",
+            self.macro_name.unraw()
+        )];
+        let mut attrs = TokenStream::new();
+        if attr.serde() {
+            docs.push(String::new());
+            docs.push(" They can be deserialized from a map of argument names to values, where absent arguments take their defaults, and unknown or missing arguments are reported as errors.".to_owned());
+        }
+        if attr.ffi() {
+            docs.push(String::new());
+            docs.push(format!(
+                " It's laid out as a C struct, so that it can be passed from C to `{0}_call`, after `{0}_args_default` has filled in its defaults.",
+                self.macro_name.unraw()
+            ));
+            attrs.extend(quote! { #[repr(C)] });
+        }
+        quote! {
+            #(#[doc = #docs])*
+            #attrs
+            #vis struct #args_name #generics #where_clause {
+                #(#fields,)*
+                #marker
+            }
+
+            #(#calls)*
+        }
+    }
+
+    /// The type of the field for `arg`, with any elided lifetimes given the struct's lifetime.
+    pub fn field_type(&self, arg: &syn::Ident) -> &syn::Type {
+        let index = self.details.args.iter().position(|a| a == arg).unwrap();
+        &self.types[index]
+    }
+
+    /// How to initialise the struct's marker field, if it has one, when constructing it.
+    pub fn marker_init(&self) -> Option<TokenStream> {
+        if self.phantoms().is_empty() {
+            None
+        } else {
+            Some(quote! { __named_marker: ::core::marker::PhantomData, })
+        }
+    }
+
+    /// Type and lifetime parameters which no argument uses still need using by the struct, which its marker field does.
+    ///
+    /// Arguments which are gated on a `#[cfg(...)]` may not exist, so don't count as using anything.
+    fn phantoms(&self) -> Vec<TokenStream> {
+        let used: Vec<_> = self
+            .details
+            .args
+            .iter()
+            .zip(&self.types)
+            .filter(|(arg, _ty)| !self.details.cfgs.contains_key(&arg.to_string()))
+            .map(|(_arg, ty)| quote! { #ty })
+            .collect();
+        let is_used = |ident: &syn::Ident, lifetime: bool| {
+            used.iter().any(|ty| mentions(ty.clone(), ident, lifetime))
+        };
+        self.generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Type(ty) if !is_used(&ty.ident, false) => {
+                    let ident = &ty.ident;
+                    Some(quote! { #ident })
+                }
+                syn::GenericParam::Lifetime(lifetime)
+                    if !is_used(&lifetime.lifetime.ident, true) =>
+                {
+                    let lifetime = &lifetime.lifetime;
+                    Some(quote! { &#lifetime () })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Wraps a call of the function, or of the struct's `call` method, in an `unsafe` block if the function is unsafe,
+    /// as the body of an `unsafe fn` isn't necessarily an unsafe context.
+    pub fn in_unsafety(&self, call: TokenStream) -> TokenStream {
+        match self.f.sig.unsafety {
+            Some(_) => quote! { unsafe { #call } },
+            None => call,
+        }
+    }
+
+    pub fn vis(&self) -> &syn::Visibility {
+        &self.f.vis
+    }
+
+    /// The function's `async` and `unsafe` qualifiers, which anything calling it through the struct also needs.
+    pub fn qualifiers(&self) -> (Option<syn::Token![async]>, Option<syn::Token![unsafe]>) {
+        (self.f.sig.asyncness, self.f.sig.unsafety)
+    }
+
+    /// What to follow a call of the function with to get its result, if it's async.
+    pub fn dot_await(&self) -> Option<TokenStream> {
+        self.f.sig.asyncness.map(|_| quote! { .await })
+    }
+}
+
+/// Whether the tokens of a type mention the type parameter `ident`, or the lifetime named `ident` if `lifetime`.
+///
+/// Identifiers which follow `::` are segments of paths, rather than parameters.
+fn mentions(tokens: TokenStream, ident: &syn::Ident, lifetime: bool) -> bool {
+    let mut previous = None;
+    for token in tokens {
+        let found = match &token {
+            TokenTree::Group(group) => mentions(group.stream(), ident, lifetime),
+            TokenTree::Ident(found) => {
+                let is_lifetime = previous == Some('\'');
+                found == ident && is_lifetime == lifetime && previous != Some(':')
+            }
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+        previous = match &token {
+            TokenTree::Punct(punct) => Some(punct.as_char()),
+            _ => None,
+        };
+    }
+    false
+}
+
+/// The name of the struct holding a function's arguments, e.g. `FooBarArgs` for `foo_bar`.
+fn args_struct_name(name: &syn::Ident) -> syn::Ident {
+    let mut camel = String::new();
+    for word in name.unraw().to_string().split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    syn::Ident::new(&format!("{}Args", camel), name.span())
+}
@@ -0,0 +1,426 @@
+#![allow(clippy::mixed_read_write_in_expression)]
+
+use indexmap::IndexMap;
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream, Result};
+
+mod kw {
+    syn::custom_keyword!(all_pub);
+    syn::custom_keyword!(cli);
+    syn::custom_keyword!(const_defaults);
+    syn::custom_keyword!(defaults);
+    syn::custom_keyword!(docs);
+    syn::custom_keyword!(ffi);
+    syn::custom_keyword!(impl_name);
+    syn::custom_keyword!(keep_name);
+    syn::custom_keyword!(macro_name);
+    syn::custom_keyword!(options);
+    syn::custom_keyword!(register);
+    syn::custom_keyword!(schema);
+    syn::custom_keyword!(serde);
+    syn::custom_keyword!(trace);
+    syn::custom_keyword!(types);
+}
+
+pub struct Attributes {
+    items: syn::punctuated::Punctuated<Attribute, syn::Token![,]>,
+}
+
+impl Attributes {
+    pub fn empty() -> Self {
+        Self {
+            items: syn::punctuated::Punctuated::new(),
+        }
+    }
+
+    /// Removes any `#[named(...)]` or `#[defaults(...)]` attributes from an item inside a trait or module, and
+    /// combines them, treating `#[defaults(...)]` as shorthand for `#[named(defaults(...))]`.
+    pub fn take_from_item(attrs: &mut Vec<syn::Attribute>) -> Result<Self> {
+        let mut taken = Self::empty();
+        let mut error: Option<syn::Error> = None;
+        attrs.retain(|attr| {
+            let parsed = if attr.path.is_ident("named") {
+                if attr.tokens.is_empty() {
+                    Ok(Self::empty())
+                } else {
+                    attr.parse_args::<Self>()
+                }
+            } else if attr.path.is_ident("defaults") {
+                let path = &attr.path;
+                let tokens = &attr.tokens;
+                syn::parse2::<Self>(quote::quote! { #path #tokens })
+            } else {
+                return true;
+            };
+            match parsed {
+                Ok(parsed) => taken.items.extend(parsed.items),
+                Err(err) => match &mut error {
+                    Some(error) => error.combine(err),
+                    None => error = Some(err),
+                },
+            }
+            false
+        });
+        match error {
+            Some(error) => Err(error),
+            None => Ok(taken),
+        }
+    }
+
+    pub fn defaults(&self) -> IndexMap<String, (proc_macro2::Span, syn::Expr)> {
+        let mut map = IndexMap::new();
+        for attribute in &self.items {
+            if let Attribute::Defaults(defaults) = attribute {
+                for default in &defaults.defaults {
+                    map.insert(
+                        default.name.to_string(),
+                        (default.name.span(), default.value.clone()),
+                    );
+                }
+            }
+        }
+        map
+    }
+
+    pub fn docs(&self) -> IndexMap<String, (proc_macro2::Span, String)> {
+        let mut map = IndexMap::new();
+        for attribute in &self.items {
+            if let Attribute::Docs(docs) = attribute {
+                for doc in &docs.docs {
+                    map.insert(doc.name.to_string(), (doc.name.span(), doc.value.value()));
+                }
+            }
+        }
+        map
+    }
+
+    pub fn types(&self) -> IndexMap<String, (proc_macro2::Span, proc_macro2::TokenStream)> {
+        let mut map = IndexMap::new();
+        for attribute in &self.items {
+            if let Attribute::Types(types) = attribute {
+                for ty in &types.types {
+                    map.insert(ty.name.to_string(), (ty.name.span(), ty.value.clone()));
+                }
+            }
+        }
+        map
+    }
+
+    pub fn const_defaults(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::ConstDefaults))
+    }
+
+    pub fn cli(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Cli))
+    }
+
+    pub fn register(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Register))
+    }
+
+    pub fn schema(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Schema))
+    }
+
+    pub fn serde(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Serde))
+    }
+
+    pub fn ffi(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Ffi))
+    }
+
+    pub fn trace(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Trace))
+    }
+
+    pub fn keep_name(&self) -> bool {
+        self.items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::KeepName))
+    }
+
+    pub fn macro_name(&self) -> Option<&syn::Ident> {
+        self.items
+            .iter()
+            .rev()
+            .find_map(|attribute| match attribute {
+                Attribute::MacroName(macro_name) => Some(&macro_name.name),
+                _ => None,
+            })
+    }
+
+    pub fn impl_name(&self) -> Option<&syn::Ident> {
+        self.items
+            .iter()
+            .rev()
+            .find_map(|attribute| match attribute {
+                Attribute::ImplName(impl_name) => Some(&impl_name.name),
+                _ => None,
+            })
+    }
+
+    pub fn options(&self) -> Option<&syn::Path> {
+        self.items
+            .iter()
+            .rev()
+            .find_map(|attribute| match attribute {
+                Attribute::Options(options) => Some(&options.path),
+                _ => None,
+            })
+    }
+}
+
+pub enum Attribute {
+    Cli,
+    ConstDefaults,
+    Defaults(Defaults),
+    Docs(Docs),
+    Ffi,
+    ImplName(ImplName),
+    KeepName,
+    MacroName(MacroName),
+    Options(Options),
+    Register,
+    Schema,
+    Serde,
+    Trace,
+    Types(Types),
+}
+
+impl Parse for Attributes {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        Ok(Self {
+            items: input.parse_terminated(Attribute::parse)?,
+        })
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::cli) {
+            input.parse::<kw::cli>().map(|_| Self::Cli)
+        } else if lookahead.peek(kw::const_defaults) {
+            input
+                .parse::<kw::const_defaults>()
+                .map(|_| Self::ConstDefaults)
+        } else if lookahead.peek(kw::defaults) {
+            input.parse().map(Self::Defaults)
+        } else if lookahead.peek(kw::docs) {
+            input.parse().map(Self::Docs)
+        } else if lookahead.peek(kw::ffi) {
+            input.parse::<kw::ffi>().map(|_| Self::Ffi)
+        } else if lookahead.peek(kw::impl_name) {
+            input.parse().map(Self::ImplName)
+        } else if lookahead.peek(kw::keep_name) {
+            input.parse::<kw::keep_name>().map(|_| Self::KeepName)
+        } else if lookahead.peek(kw::macro_name) {
+            input.parse().map(Self::MacroName)
+        } else if lookahead.peek(kw::options) {
+            input.parse().map(Self::Options)
+        } else if lookahead.peek(kw::register) {
+            input.parse::<kw::register>().map(|_| Self::Register)
+        } else if lookahead.peek(kw::schema) {
+            input.parse::<kw::schema>().map(|_| Self::Schema)
+        } else if lookahead.peek(kw::serde) {
+            input.parse::<kw::serde>().map(|_| Self::Serde)
+        } else if lookahead.peek(kw::trace) {
+            input.parse::<kw::trace>().map(|_| Self::Trace)
+        } else if lookahead.peek(kw::types) {
+            input.parse().map(Self::Types)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+pub struct Default {
+    name: syn::Ident,
+    _eq_token: syn::Token![=],
+    value: syn::Expr,
+}
+
+impl Parse for Default {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            name: input.parse()?,
+            _eq_token: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+pub struct Defaults {
+    _keyword: kw::defaults,
+    _bracket_token: syn::token::Paren,
+    defaults: syn::punctuated::Punctuated<Default, syn::Token![,]>,
+}
+
+impl Parse for Defaults {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            _keyword: input.parse()?,
+            _bracket_token: syn::parenthesized!(content in input),
+            defaults: content.parse_terminated(Default::parse)?,
+        })
+    }
+}
+
+pub struct Doc {
+    name: syn::Ident,
+    _eq_token: syn::Token![=],
+    value: syn::LitStr,
+}
+
+impl Parse for Doc {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            name: input.parse()?,
+            _eq_token: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+pub struct Docs {
+    _keyword: kw::docs,
+    _bracket_token: syn::token::Paren,
+    docs: syn::punctuated::Punctuated<Doc, syn::Token![,]>,
+}
+
+impl Parse for Docs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            _keyword: input.parse()?,
+            _bracket_token: syn::parenthesized!(content in input),
+            docs: content.parse_terminated(Doc::parse)?,
+        })
+    }
+}
+
+pub struct TypeDefault {
+    name: syn::Ident,
+    _eq_token: syn::Token![=],
+    /// Either a type, for a type parameter, or a const expression, for a const parameter.
+    value: proc_macro2::TokenStream,
+}
+
+impl Parse for TypeDefault {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            name: input.parse()?,
+            _eq_token: input.parse()?,
+            value: {
+                let fork = input.fork();
+                if fork.parse::<syn::Type>().is_ok()
+                    && (fork.is_empty() || fork.peek(syn::Token![,]))
+                {
+                    input.parse::<syn::Type>()?.into_token_stream()
+                } else {
+                    input.parse::<syn::Expr>()?.into_token_stream()
+                }
+            },
+        })
+    }
+}
+
+pub struct Types {
+    _keyword: kw::types,
+    _bracket_token: syn::token::Paren,
+    types: syn::punctuated::Punctuated<TypeDefault, syn::Token![,]>,
+}
+
+impl Parse for Types {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            _keyword: input.parse()?,
+            _bracket_token: syn::parenthesized!(content in input),
+            types: content.parse_terminated(TypeDefault::parse)?,
+        })
+    }
+}
+
+pub struct Options {
+    _keyword: kw::options,
+    _eq_token: syn::Token![=],
+    path: syn::Path,
+}
+
+impl Parse for Options {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            _keyword: input.parse()?,
+            _eq_token: input.parse()?,
+            path: input.parse()?,
+        })
+    }
+}
+
+pub struct MacroName {
+    _keyword: kw::macro_name,
+    _eq_token: syn::Token![=],
+    name: syn::Ident,
+}
+
+impl Parse for MacroName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            _keyword: input.parse()?,
+            _eq_token: input.parse()?,
+            name: input.parse()?,
+        })
+    }
+}
+
+pub struct ImplName {
+    _keyword: kw::impl_name,
+    _eq_token: syn::Token![=],
+    name: syn::Ident,
+}
+
+impl Parse for ImplName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            _keyword: input.parse()?,
+            _eq_token: input.parse()?,
+            name: input.parse()?,
+        })
+    }
+}
+
+/// Arguments to `#[named_module(...)]`.
+pub struct ModuleAttributes {
+    pub all_pub: bool,
+}
+
+impl Parse for ModuleAttributes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut all_pub = false;
+        while !input.is_empty() {
+            input.parse::<kw::all_pub>()?;
+            all_pub = true;
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(Self { all_pub })
+    }
+}
@@ -0,0 +1,750 @@
+use crate::arg_reconciler::ArgDetails;
+use indexmap::IndexMap;
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+/// Generates the macro which hands a function's signature to `named::call!` and `named::signature!`.
+///
+/// Those macros can't see the function's definition, so instead invokes this macro with itself as a callback, which
+/// passes the signature back alongside the call being made.
+pub fn generate_signature_macro(
+    dunder_name: &syn::Ident,
+    target: &dyn ToTokens,
+    details: &ArgDetails,
+) -> TokenStream {
+    // Name of the macro which reports the function's signature.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let signature_name = signature_macro_name(dunder_name);
+    let default_fns = &details.default_fns;
+    let generics = details.generics.iter().map(|param| {
+        let constness = if param.is_const {
+            Some(quote! { const })
+        } else {
+            None
+        };
+        let name = &param.name;
+        let default = param
+            .default
+            .as_ref()
+            .map(|default| quote! { = [#default] });
+        quote! { #constness #name #default }
+    });
+    let args = details.args.iter().zip(&details.types).map(|(arg, ty)| {
+        let default = details.defaults[&arg.to_string()]
+            .as_ref()
+            .map(|default| quote! { = [#default] });
+        quote! { #arg: [#ty] #default }
+    });
+    let options = details.options.as_ref().map(|options| {
+        let name = &options.name;
+        let path = &options.path;
+        quote! { #name #path }
+    });
+    let disabled = details.disabled.iter().map(|(arg, cfg)| {
+        let message = crate::disabled_message(arg, cfg);
+        quote! { #arg = #message }
+    });
+    let traced = if details.traced {
+        Some(quote! { trace })
+    } else {
+        None
+    };
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #signature_name {
+            ([$($callback:tt)*] $($call:tt)*) => {
+                $($callback)*! {
+                    [#target] [#default_fns] [#(#generics),*] [#(#args),*] [#options] [#(#disabled),*] [#traced]
+                    $($call)*
+                }
+            };
+        }
+    }
+}
+
+/// Generates a signature macro named for `alias_dunder_name`, which reports the signature of the function whose helper
+/// macros are named for `dunder_name`.
+pub fn generate_signature_alias(
+    alias_dunder_name: &syn::Ident,
+    dunder_name: &syn::Ident,
+) -> TokenStream {
+    let alias_name = signature_macro_name(alias_dunder_name);
+    let signature_name = signature_macro_name(dunder_name);
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #alias_name {
+            ($($input:tt)*) => { #signature_name! { $($input)* } };
+        }
+    }
+}
+
+pub fn signature_macro_name(dunder_name: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&format!("{}_signature", dunder_name), dunder_name.span())
+}
+
+/// A call of a named-argument function, either `path::to::function::<T = u8>(a = 1, ..base)` or
+/// `receiver.Trait::method(a = 1)`, in which case the receiver is passed as the argument named `self`.
+pub struct Call {
+    receiver: Option<syn::Expr>,
+    path: syn::Path,
+    args: Punctuated<syn::Expr, syn::Token![,]>,
+}
+
+impl Parse for Call {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let tokens: Vec<TokenTree> = input.parse::<TokenStream>()?.into_iter().collect();
+        // Method syntax names the method's trait, which isn't an expression Rust has, so the receiver is split off at
+        // the `.` before the trait's path.
+        let (receiver, call) = match qualified_method_dot(&tokens) {
+            Some(dot) => (
+                Some(syn::parse2::<syn::Expr>(
+                    tokens[..dot].iter().cloned().collect(),
+                )?),
+                &tokens[dot + 1..],
+            ),
+            None => (None, &tokens[..]),
+        };
+        let expr: syn::Expr = syn::parse2(call.iter().cloned().collect())?;
+        match expr {
+            syn::Expr::Call(call) => match *call.func {
+                syn::Expr::Path(func) if func.qself.is_none() => Ok(Self {
+                    receiver,
+                    path: func.path,
+                    args: call.args,
+                }),
+                func => Err(syn::Error::new_spanned(
+                    func,
+                    "Expected the path of a named function",
+                )),
+            },
+            syn::Expr::MethodCall(call) => Err(syn::Error::new(
+                call.method.span(),
+                format!(
+                    "Methods called with method syntax must be qualified by their trait, as in `receiver.Trait::{}(...)`, so that they can't be mistaken for another function of the same name",
+                    call.method.unraw()
+                ),
+            )),
+            expr => Err(syn::Error::new_spanned(
+                expr,
+                "Expected a call, like `function(name = value)` or `receiver.Trait::method(name = value)`",
+            )),
+        }
+    }
+}
+
+/// The index of the `.` which separates a method call's receiver from the method's trait-qualified path, as in
+/// `receiver.Trait::method(...)`, if the call is one.
+///
+/// A `.` followed by a path can't otherwise appear outside of brackets: a field is never followed by `::`, and a
+/// method's turbofish is followed by `<`.
+fn qualified_method_dot(tokens: &[TokenTree]) -> Option<usize> {
+    let is_punct = |index: usize, ch: char, spacing: Spacing| matches!(tokens.get(index), Some(TokenTree::Punct(punct)) if punct.as_char() == ch && punct.spacing() == spacing);
+    (1..tokens.len()).rev().find(|&dot| {
+        is_punct(dot, '.', Spacing::Alone)
+            && !is_punct(dot - 1, '.', Spacing::Joint)
+            && matches!(tokens.get(dot + 1), Some(TokenTree::Ident(_)))
+            && is_punct(dot + 2, ':', Spacing::Joint)
+            && is_punct(dot + 3, ':', Spacing::Alone)
+            && matches!(tokens.get(dot + 4), Some(TokenTree::Ident(_)))
+    })
+}
+
+impl Call {
+    /// The path of the macro which reports the signature of the function being called.
+    pub fn signature_macro(&self) -> syn::Path {
+        if self.receiver.is_none() {
+            return signature_macro_path(&self.path);
+        }
+        // A method's signature macro is named for its trait and itself, and found through the trait's path.
+        let mut path = self.path.clone();
+        // Unwrap OK: a qualified method's path always has at least two segments.
+        let method = path.segments.pop().unwrap().into_value().ident;
+        let trait_name = path.segments.pop().unwrap().into_value().ident;
+        let dunder_name = method_dunder_name(&trait_name, &method);
+        path.segments
+            .push(signature_macro_name(&dunder_name).into());
+        path
+    }
+}
+
+/// The name which the helper macros of a `#[named_trait]` method are named for by default, and which its signature
+/// macro is always found by.
+pub fn method_dunder_name(trait_name: &syn::Ident, method: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(
+        &format!("__{}_{}", trait_name.unraw(), method.unraw()),
+        method.span(),
+    )
+}
+
+/// The path of the macro which reports the signature of the named function at `path`.
+pub fn signature_macro_path(path: &syn::Path) -> syn::Path {
+    let mut path = path.clone();
+    // Unwrap OK: a parsed path always has at least one segment.
+    let last = path.segments.last_mut().unwrap();
+    let dunder_name = syn::Ident::new(&format!("__{}", last.ident.unraw()), last.ident.span());
+    last.ident = signature_macro_name(&dunder_name);
+    last.arguments = syn::PathArguments::None;
+    path
+}
+
+/// The signature reported by a function's signature macro.
+pub struct Signature {
+    target: TokenStream,
+    default_fns: Option<syn::Ident>,
+    generics: Vec<GenericParam>,
+    args: Vec<Arg>,
+    options: Option<(syn::Ident, syn::Path)>,
+    disabled: IndexMap<String, syn::LitStr>,
+    /// Whether the target is a `#[named(trace)]` wrapper, which takes each argument wrapped in a
+    /// `named::__private::trace::Arg`.
+    traced: bool,
+}
+
+struct Arg {
+    name: syn::Ident,
+    ty: TokenStream,
+    default: Option<TokenStream>,
+}
+
+/// The signature reported by a function's signature macro, followed by the call being made.
+pub struct SignatureAndCall {
+    signature: Signature,
+    call: Call,
+}
+
+struct GenericParam {
+    name: syn::Ident,
+    is_const: bool,
+    default: Option<TokenStream>,
+}
+
+fn bracketed(input: ParseStream) -> syn::Result<TokenStream> {
+    let content;
+    syn::bracketed!(content in input);
+    content.parse()
+}
+
+/// An optional `= [default]` after a parameter or argument.
+fn default(input: ParseStream) -> syn::Result<Option<TokenStream>> {
+    if input.peek(syn::Token![=]) {
+        input.parse::<syn::Token![=]>()?;
+        Ok(Some(bracketed(input)?))
+    } else {
+        Ok(None)
+    }
+}
+
+impl Parse for Signature {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let target = bracketed(input)?;
+        let default_fns = syn::parse2(bracketed(input)?)?;
+
+        let content;
+        syn::bracketed!(content in input);
+        let generics = Punctuated::<_, syn::Token![,]>::parse_terminated_with(&content, |input| {
+            let is_const = input.parse::<Option<syn::Token![const]>>()?.is_some();
+            Ok(GenericParam {
+                name: input.parse()?,
+                is_const,
+                default: default(input)?,
+            })
+        })?;
+
+        let content;
+        syn::bracketed!(content in input);
+        let args = Punctuated::<_, syn::Token![,]>::parse_terminated_with(&content, |input| {
+            let name = syn::Ident::parse_any(input)?;
+            input.parse::<syn::Token![:]>()?;
+            Ok(Arg {
+                name,
+                ty: bracketed(input)?,
+                default: default(input)?,
+            })
+        })?;
+
+        let content;
+        syn::bracketed!(content in input);
+        let options = if content.is_empty() {
+            None
+        } else {
+            Some((content.parse()?, content.parse()?))
+        };
+
+        let content;
+        syn::bracketed!(content in input);
+        let disabled = Punctuated::<_, syn::Token![,]>::parse_terminated_with(&content, |input| {
+            let arg = syn::Ident::parse_any(input)?;
+            input.parse::<syn::Token![=]>()?;
+            Ok((arg.to_string(), input.parse()?))
+        })?;
+
+        let traced = !bracketed(input)?.is_empty();
+
+        Ok(Self {
+            target,
+            default_fns,
+            generics: generics.into_iter().collect(),
+            args: args.into_iter().collect(),
+            options,
+            disabled: disabled.into_iter().collect(),
+            traced,
+        })
+    }
+}
+
+impl Signature {
+    /// A `named::Signature` describing the function, as a constant expression.
+    pub fn describe(&self, name: &syn::Ident) -> TokenStream {
+        describe(
+            name,
+            self.args
+                .iter()
+                .map(|arg| (&arg.name, &arg.ty as &dyn ToTokens, arg.default.as_ref())),
+        )
+    }
+}
+
+/// A `named::Signature` describing the function whose macro is `name`, from each of its parameters' names, types and
+/// defaults, as a constant expression.
+pub fn describe<'a>(
+    name: &syn::Ident,
+    params: impl Iterator<Item = (&'a syn::Ident, &'a dyn ToTokens, Option<&'a TokenStream>)>,
+) -> TokenStream {
+    let name = name.unraw().to_string();
+    let params = params.map(|(name, ty, default)| {
+        let name = name.to_string();
+        let ty = crate::docs::type_string(ty);
+        let required = default.is_none();
+        let default = match default {
+            Some(default) => {
+                let default = crate::docs::expr_string(default);
+                quote! { ::core::option::Option::Some(#default) }
+            }
+            None => quote! { ::core::option::Option::None },
+        };
+        quote! {
+            ::named::Param {
+                name: #name,
+                ty: #ty,
+                required: #required,
+                default: #default,
+            }
+        }
+    });
+    quote! {
+        ::named::Signature {
+            name: #name,
+            params: &[#(#params),*],
+        }
+    }
+}
+
+impl Parse for SignatureAndCall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            signature: input.parse()?,
+            call: input.parse()?,
+        })
+    }
+}
+
+impl SignatureAndCall {
+    pub fn expand(mut self) -> syn::Result<TokenStream> {
+        let mut errors = Errors::default();
+        let arg_names: Vec<String> = self
+            .signature
+            .args
+            .iter()
+            .map(|arg| arg.name.to_string())
+            .collect();
+        let turbofish = self.turbofish(&mut errors);
+
+        // Sort what was given into the function's own arguments, fields of its options struct, and a spread struct.
+        let mut given: IndexMap<String, (syn::Ident, syn::Expr)> = IndexMap::new();
+        let mut fields = Vec::new();
+        let mut base = None;
+        // Where each of the values given, other than the receiver, was sorted to, in the order they were given.
+        let mut given_order = Vec::new();
+        // A method call's receiver is passed as the argument named `self`.
+        let method_syntax = self.call.receiver.is_some();
+        let receiver = self.call.receiver.take().map(|receiver| {
+            let name = syn::Ident::new("self", receiver.span());
+            syn::Expr::Assign(syn::parse_quote! { #name = #receiver })
+        });
+        let args = std::mem::take(&mut self.call.args);
+        for arg in receiver.into_iter().chain(args) {
+            let (name, value) = match arg {
+                syn::Expr::Assign(assign) => match *assign.left {
+                    syn::Expr::Path(path)
+                        if path.qself.is_none() && path.path.get_ident().is_some() =>
+                    {
+                        // Unwrap OK: checked in the guard above.
+                        (path.path.get_ident().unwrap().clone(), *assign.right)
+                    }
+                    left => {
+                        errors.push(left.span(), "Expected the name of an argument");
+                        continue;
+                    }
+                },
+                syn::Expr::Range(syn::ExprRange {
+                    from: None,
+                    limits: syn::RangeLimits::HalfOpen(_),
+                    to: Some(to),
+                    ..
+                }) if base.is_none() => {
+                    base = Some(*to);
+                    given_order.push(Given::Base);
+                    continue;
+                }
+                other => {
+                    errors.push(
+                        other.span(),
+                        "Expected a named argument, like `name = value`, or a struct to take unspecified arguments from, like `..base`",
+                    );
+                    continue;
+                }
+            };
+            let key = name.unraw().to_string();
+            if given.contains_key(&key)
+                || fields
+                    .iter()
+                    .any(|(field, _): &(syn::Ident, _)| field == &name)
+            {
+                errors.push(
+                    name.span(),
+                    format!("Argument `{}` was given more than once", name),
+                );
+            } else if let Some(message) = self.signature.disabled.get(&key) {
+                errors.push(name.span(), message.value());
+            } else if arg_names.iter().any(|arg| arg == &key) {
+                // A receiver which is a place is borrowed by the call as usual, so is left where it is.
+                let is_receiver = method_syntax && given.is_empty();
+                let never_bound = is_closure(&value) || (is_receiver && is_place(&value));
+                if !never_bound {
+                    given_order.push(Given::Arg(key.clone()));
+                }
+                given.insert(key, (name, value));
+            } else if self.signature.options.is_some() {
+                if !is_closure(&value) {
+                    given_order.push(Given::Field(fields.len()));
+                }
+                fields.push((name, value));
+            } else {
+                errors.push(
+                    name.span(),
+                    format!(
+                        "Unrecognized named argument - got value for argument `{}` but only expected {}",
+                        name,
+                        crate::format_names(&arg_names),
+                    ),
+                );
+            }
+        }
+
+        let base_name = quote! { __named_base };
+
+        // Values are evaluated in the order they were given. Where that isn't the order they'll be passed in, the values
+        // up to the last one which is out of order are bound to temporaries first, with a match so that any temporaries
+        // they borrow live until the call is complete, and the rest are passed straight in. Closures are never bound, as
+        // creating one has no side effects, and binding one would stop it inferring its parameter types.
+        let position = |given: &Given| match given {
+            Given::Base => None,
+            Given::Arg(key) => arg_names.iter().position(|arg| arg == key),
+            Given::Field(_) => self
+                .signature
+                .args
+                .iter()
+                .position(|Arg { name: arg, .. }| {
+                    self.signature
+                        .options
+                        .as_ref()
+                        .is_some_and(|(options_arg, _path)| options_arg == arg)
+                }),
+        };
+        let mut unbound = given_order.len();
+        while unbound > 0
+            && match given_order.get(unbound) {
+                Some(next) => position(&given_order[unbound - 1]) <= position(next),
+                None => true,
+            }
+        {
+            unbound -= 1;
+        }
+        let mut bindings = Vec::new();
+        for (index, given_value) in given_order[..unbound].iter().enumerate() {
+            let temporary =
+                syn::Ident::new(&format!("__named_value_{}", index), Span::mixed_site());
+            let value = match given_value {
+                Given::Base => {
+                    // Unwrap OK: the base is always recorded along with its value.
+                    let base = base.as_mut().unwrap();
+                    let temporary: syn::Expr = syn::parse_quote! { #temporary };
+                    std::mem::replace(base, temporary)
+                }
+                Given::Arg(key) => {
+                    let value = &mut given[key].1;
+                    std::mem::replace(value, syn::parse_quote! { #temporary })
+                }
+                Given::Field(index) => {
+                    let value = &mut fields[*index].1;
+                    std::mem::replace(value, syn::parse_quote! { #temporary })
+                }
+            };
+            bindings.push((temporary, value));
+        }
+        let default_value =
+            |arg: &syn::Ident, default: &TokenStream| match &self.signature.default_fns {
+                Some(default_fns) => quote! { #default_fns::#arg #turbofish() },
+                None => quote! { #default },
+            };
+        let traced = self.signature.traced;
+
+        let mut missing = Vec::new();
+        let mut values = Vec::new();
+        for Arg {
+            name: arg, default, ..
+        } in &self.signature.args
+        {
+            let key = arg.to_string();
+            let is_options = self
+                .signature
+                .options
+                .as_ref()
+                .is_some_and(|(options_arg, _path)| options_arg == arg);
+            if is_options && !fields.is_empty() {
+                let (_options_arg, path) = self.signature.options.as_ref().unwrap();
+                if let Some((name, _value)) = given.get(&key) {
+                    errors.push(
+                        name.span(),
+                        format!(
+                            "Cannot specify fields of `{}` when also specifying `{}`",
+                            path.to_token_stream(),
+                            arg,
+                        ),
+                    );
+                    continue;
+                }
+                let rest = match (&base, default) {
+                    (Some(_), _) => quote! { #base_name.#arg },
+                    // Unwrap OK: reconcile always gives the options struct a default.
+                    (None, default) => default_value(arg, default.as_ref().unwrap()),
+                };
+                let fields = fields
+                    .iter()
+                    .map(|(field, value)| quote! { #field: #value });
+                values.push(crate::trace::given(
+                    traced,
+                    quote! { #path { #(#fields,)* ..#rest } },
+                ));
+            } else if let Some((_name, value)) = given.get(&key) {
+                values.push(crate::trace::given(traced, value.to_token_stream()));
+            } else if base.is_some() && key != "self" {
+                values.push(crate::trace::given(traced, quote! { #base_name.#arg }));
+            } else if let Some(default) = default {
+                values.push(crate::trace::defaulted(traced, default_value(arg, default)));
+            } else {
+                missing.push(key);
+            }
+        }
+        if !missing.is_empty() {
+            let maybe_s = if missing.len() == 1 { "" } else { "s" };
+            errors.push(
+                self.call.path.span(),
+                format!(
+                    "Must specify value{} for non-defaulted argument{}: {}",
+                    maybe_s,
+                    maybe_s,
+                    crate::format_names(&missing),
+                ),
+            );
+        }
+        errors.finish()?;
+
+        let target = &self.signature.target;
+        let call = match syn::parse2::<syn::Path>(target.clone()) {
+            // Calling the method with method syntax lets the receiver be auto-referenced, as it would be by an ordinary
+            // method call.
+            Ok(path)
+                if method_syntax
+                    && self
+                        .signature
+                        .args
+                        .first()
+                        .is_some_and(|arg| arg.name == "self") =>
+            {
+                // Unwrap OK: a parsed path always has at least one segment.
+                let method = &path.segments.last().unwrap().ident;
+                let receiver = &values[0];
+                let values = &values[1..];
+                quote! { (#receiver).#method #turbofish(#(#values),*) }
+            }
+            _ => quote! { #target #turbofish(#(#values),*) },
+        };
+        let call = match base {
+            Some(base) => quote! { { let #base_name = #base; #call } },
+            None => call,
+        };
+        if bindings.is_empty() {
+            return Ok(call);
+        }
+        // The receiver may be bound, and mutably borrowed by the call.
+        let (temporaries, values): (Vec<_>, Vec<_>) = bindings.into_iter().unzip();
+        Ok(quote! {
+            match (#(#values,)*) {
+                #[allow(unused_mut)]
+                (#(mut #temporaries,)*) => #call,
+            }
+        })
+    }
+
+    /// The generic arguments to call the function with, taken from the call if given, or else from the parameters'
+    /// defaults, or else left to be inferred.
+    fn turbofish(&self, errors: &mut Errors) -> TokenStream {
+        if self.signature.generics.is_empty() {
+            return TokenStream::new();
+        }
+        let mut values: Vec<Option<TokenStream>> = vec![None; self.signature.generics.len()];
+        let last = self.call.path.segments.last();
+        if let Some(syn::PathArguments::AngleBracketed(generics)) = last.map(|last| &last.arguments)
+        {
+            let mut positional = 0;
+            for arg in &generics.args {
+                let index = match arg {
+                    syn::GenericArgument::Binding(binding) => {
+                        match self
+                            .signature
+                            .generics
+                            .iter()
+                            .position(|param| param.name == binding.ident)
+                        {
+                            Some(index) => {
+                                if values[index].is_some() {
+                                    errors.push(
+                                        binding.ident.span(),
+                                        format!(
+                                            "Generic argument `{}` was given more than once",
+                                            binding.ident
+                                        ),
+                                    );
+                                }
+                                values[index] = Some(binding.ty.to_token_stream());
+                                continue;
+                            }
+                            None => {
+                                errors.push(
+                                    binding.ident.span(),
+                                    format!(
+                                        "Unrecognized generic argument - got value for `{}` but only expected {}",
+                                        binding.ident,
+                                        crate::format_names(
+                                            &self
+                                                .signature.generics
+                                                .iter()
+                                                .map(|param| param.name.to_string())
+                                                .collect::<Vec<_>>()
+                                        ),
+                                    ),
+                                );
+                                continue;
+                            }
+                        }
+                    }
+                    syn::GenericArgument::Type(_) | syn::GenericArgument::Const(_) => {
+                        positional += 1;
+                        positional - 1
+                    }
+                    other => {
+                        errors.push(other.span(), "Expected a type or const generic argument");
+                        continue;
+                    }
+                };
+                match values.get_mut(index) {
+                    Some(value) => *value = Some(arg.to_token_stream()),
+                    None => errors.push(
+                        arg.span(),
+                        format!(
+                            "Too many generic arguments - expected at most {}",
+                            self.signature.generics.len()
+                        ),
+                    ),
+                }
+            }
+        }
+        let values = self
+            .signature
+            .generics
+            .iter()
+            .zip(values)
+            .map(|(param, value)| {
+                match (value.or_else(|| param.default.clone()), param.is_const) {
+                    // Const arguments in a turbofish need braces unless they're a literal or a single identifier, so always add them.
+                    (Some(value), true) => quote! { { #value } },
+                    (Some(value), false) => value,
+                    (None, _) => quote! { _ },
+                }
+            });
+        quote! { ::<#(#values),*> }
+    }
+}
+
+fn is_closure(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Closure(_) => true,
+        syn::Expr::Paren(paren) => is_closure(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Whether an expression is a place, like `total`, `self.total` or `totals[0]`, rather than a value.
+fn is_place(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Path(_) | syn::Expr::Index(_) => true,
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Deref(_),
+            ..
+        }) => true,
+        syn::Expr::Field(field) => is_place(&field.base),
+        syn::Expr::Paren(paren) => is_place(&paren.expr),
+        _ => false,
+    }
+}
+
+/// A value given in a call, by where it was sorted to.
+enum Given {
+    /// The struct which unspecified arguments are taken from.
+    Base,
+    /// One of the function's own arguments.
+    Arg(String),
+    /// The field of the function's options struct at this index in the fields given.
+    Field(usize),
+}
+
+/// Errors found while matching a call to its signature, which are all reported together.
+#[derive(Default)]
+struct Errors {
+    error: Option<syn::Error>,
+}
+
+impl Errors {
+    fn push(&mut self, span: Span, message: impl std::fmt::Display) {
+        let error = syn::Error::new(span, message);
+        match &mut self.error {
+            Some(existing) => existing.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    fn finish(self) -> syn::Result<()> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
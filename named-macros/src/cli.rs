@@ -0,0 +1,232 @@
+use crate::arg_reconciler::ArgDetails;
+use crate::args_struct::ArgsStruct;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+/// Generates the items for `#[named(cli)]`: implementations of clap's `Args` and `FromArgMatches` for the arguments
+/// struct, so that it can be parsed from the command line, a `foo_main_from` function which parses the given command
+/// line and calls the function, and a `foo_main` function which does so with the process's own command line.
+///
+/// Each argument becomes a `--flag`, named after the argument with its underscores replaced by dashes. Required
+/// arguments are required flags, arguments with defaults take them when their flag isn't given, `Option` arguments are
+/// `None` when their flag isn't given, and `bool` arguments are switches which take no value, so can only default to
+/// `false`. Parsed values are owned, so arguments can't be borrowed.
+pub fn generate(args_struct: &ArgsStruct, details: &ArgDetails) -> syn::Result<TokenStream> {
+    let clap = quote! { ::named::__private::clap };
+    let args_name = &args_struct.name;
+    let name = args_struct.macro_name.unraw().to_string();
+
+    let mut augments = Vec::new();
+    let mut fields = Vec::new();
+    let mut updates = Vec::new();
+    for (arg, ty) in details.args.iter().zip(&details.types) {
+        let key = arg.to_string();
+        let id = arg.unraw().to_string();
+        let long = id.replace('_', "-");
+        let cfg = details.cfgs.get(&key).map(|cfg| quote! { #[cfg(#cfg)] });
+        if let syn::Type::Reference(_) = option_inner(ty).unwrap_or(ty) {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`cli` does not support borrowed arguments, as values parsed from the command line are owned - take an owned type, like `String` rather than `&str`, instead.",
+            ));
+        }
+        let default = details.defaults[&key].as_ref();
+        if let Some(default) =
+            default.filter(|default| is_bool(ty) && default.to_string() != "false")
+        {
+            return Err(syn::Error::new_spanned(
+                default,
+                "`cli` only supports `bool` arguments defaulting to `false`, as they're switches, which can only be turned on from the command line.",
+            ));
+        }
+        let default_value = match (default, &details.default_fns) {
+            (Some(_), Some(default_fns)) => Some(quote! { #default_fns::#arg() }),
+            _ => None,
+        };
+
+        // Defaults are shown as clap shows its own, as their values rather than their source.
+        let description = details
+            .descriptions
+            .get(&key)
+            .map(|description| description.trim().to_owned())
+            .unwrap_or_default();
+        let help = match &default_value {
+            Some(default_value) if !is_bool(ty) => Some(quote! {
+                .help(::named::__private::cli::help(#description, ::named::__cli_default!(#default_value)))
+            }),
+            _ if !description.is_empty() => Some(quote! { .help(#description) }),
+            _ => None,
+        };
+
+        let (value, value_parser, required) = if is_bool(ty) {
+            let value = quote! { matches.get_flag(#id) };
+            updates.push(quote! {
+                #cfg
+                if matches.get_flag(#id) {
+                    self.#arg = true;
+                }
+            });
+            (value, quote! { .action(#clap::ArgAction::SetTrue) }, false)
+        } else {
+            let (parsed, optional) = match option_inner(ty) {
+                Some(inner) => (inner, true),
+                None => (ty, false),
+            };
+            let given = if optional {
+                quote! { ::core::option::Option::Some(value) }
+            } else {
+                quote! { value }
+            };
+            let absent = match (&default_value, optional) {
+                (Some(default_value), _) => default_value.clone(),
+                (None, true) => quote! { ::core::option::Option::None },
+                (None, false) => quote! {
+                    return ::core::result::Result::Err(#clap::Error::raw(
+                        #clap::error::ErrorKind::MissingRequiredArgument,
+                        ::core::concat!("The following required argument was not provided: ", #id),
+                    ))
+                },
+            };
+            let value = quote! {
+                match matches.remove_one::<#parsed>(#id) {
+                    ::core::option::Option::Some(value) => #given,
+                    ::core::option::Option::None => #absent,
+                }
+            };
+            updates.push(quote! {
+                #cfg
+                if let ::core::option::Option::Some(value) = matches.remove_one::<#parsed>(#id) {
+                    self.#arg = #given;
+                }
+            });
+            let required = default_value.is_none() && !optional;
+            let value_name = id.to_uppercase();
+            (
+                value,
+                quote! {
+                    .value_name(#value_name)
+                    .value_parser(#clap::value_parser!(#parsed))
+                },
+                required,
+            )
+        };
+        augments.push((
+            cfg.clone(),
+            quote! { #clap::Arg::new(#id).long(#long) #value_parser #help },
+            required,
+        ));
+        fields.push(quote! { #cfg #arg: #value });
+    }
+    let augment = |for_update: bool| {
+        augments.iter().map(move |(cfg, arg, required)| {
+            let required = *required && !for_update;
+            quote! {
+                #cfg
+                let command = command.arg(#arg.required(#required));
+            }
+        })
+    };
+    let augment_args = augment(false);
+    let augment_args_for_update = augment(true);
+
+    let vis = args_struct.vis();
+    let (asyncness, unsafety) = args_struct.qualifiers();
+    let output = &args_struct.output;
+    let call = args_struct.in_unsafety(quote! { args.call() });
+    let dot_await = args_struct.dot_await();
+    let main = syn::Ident::new(
+        &format!("{}_main", args_struct.macro_name.unraw()),
+        args_struct.macro_name.span(),
+    );
+    let main_from = syn::Ident::new(
+        &format!("{}_main_from", args_struct.macro_name.unraw()),
+        args_struct.macro_name.span(),
+    );
+    let main_doc = format!(
+        " Calls `{}!` with its arguments parsed from the process's command line, exiting with a usage message if they can't be parsed.",
+        name
+    );
+    let main_from_doc = format!(
+        " Calls `{}!` with its arguments parsed from `args`, the first of which is the binary's name, exiting with a usage message if they can't be parsed.",
+        name
+    );
+    let call_main_from = args_struct.in_unsafety(quote! { #main_from(::std::env::args_os()) });
+    Ok(quote! {
+        ::named::__require_cli! {
+            impl #clap::FromArgMatches for #args_name {
+                fn from_arg_matches(matches: &#clap::ArgMatches) -> ::core::result::Result<Self, #clap::Error> {
+                    Self::from_arg_matches_mut(&mut matches.clone())
+                }
+
+                fn from_arg_matches_mut(matches: &mut #clap::ArgMatches) -> ::core::result::Result<Self, #clap::Error> {
+                    ::core::result::Result::Ok(Self {
+                        #(#fields,)*
+                    })
+                }
+
+                fn update_from_arg_matches(&mut self, matches: &#clap::ArgMatches) -> ::core::result::Result<(), #clap::Error> {
+                    self.update_from_arg_matches_mut(&mut matches.clone())
+                }
+
+                fn update_from_arg_matches_mut(&mut self, matches: &mut #clap::ArgMatches) -> ::core::result::Result<(), #clap::Error> {
+                    #(#updates)*
+                    ::core::result::Result::Ok(())
+                }
+            }
+
+            impl #clap::Args for #args_name {
+                fn augment_args(command: #clap::Command) -> #clap::Command {
+                    #(#augment_args)*
+                    command
+                }
+
+                fn augment_args_for_update(command: #clap::Command) -> #clap::Command {
+                    #(#augment_args_for_update)*
+                    command
+                }
+            }
+
+            #[doc = #main_from_doc]
+            #vis #asyncness #unsafety fn #main_from<__I, __T>(args: __I) -> #output
+            where
+                __I: ::core::iter::IntoIterator<Item = __T>,
+                __T: ::core::convert::Into<::std::ffi::OsString> + ::core::clone::Clone,
+            {
+                let mut command = <#args_name as #clap::Args>::augment_args(#clap::Command::new(#name));
+                let mut matches = command.clone().get_matches_from(args);
+                let args = <#args_name as #clap::FromArgMatches>::from_arg_matches_mut(&mut matches)
+                    .unwrap_or_else(|err| err.format(&mut command).exit());
+                #call #dot_await
+            }
+
+            #[doc = #main_doc]
+            #vis #asyncness #unsafety fn #main() -> #output {
+                #call_main_from #dot_await
+            }
+        }
+    })
+}
+
+fn is_bool(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
+
+/// The `T` of an `Option<T>`.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
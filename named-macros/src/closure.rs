@@ -0,0 +1,125 @@
+use crate::attr_parser::Attributes;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+/// `mut name = move |arg: Type = default, ...| body`
+pub struct Closure {
+    pub mutability: Option<syn::Token![mut]>,
+    pub name: syn::Ident,
+    _eq_token: syn::Token![=],
+    pub capture: Option<syn::Token![move]>,
+    _or1_token: syn::Token![|],
+    pub params: Punctuated<Param, syn::Token![,]>,
+    _or2_token: syn::Token![|],
+    pub body: TokenStream,
+}
+
+/// `arg`, `arg: Type`, `arg = default` or `arg: Type = default`
+pub struct Param {
+    pub name: syn::Ident,
+    pub ty: Option<syn::Type>,
+    pub default: Option<TokenStream>,
+}
+
+impl Closure {
+    /// The closure's parameters as if they were a function's, for reconciling with its defaults.
+    ///
+    /// Parameters without types are given the type `_`, which is only used for documentation.
+    pub fn inputs(&self) -> Punctuated<syn::FnArg, syn::Token![,]> {
+        self.params
+            .iter()
+            .map(|param| -> syn::FnArg {
+                let name = &param.name;
+                let ty = match &param.ty {
+                    Some(ty) => quote! { #ty },
+                    None => quote! { _ },
+                };
+                syn::parse_quote! { #name: #ty }
+            })
+            .collect()
+    }
+
+    /// The defaults written inline in the closure's parameters, as if they'd been given in `#[named(defaults(...))]`.
+    pub fn attr(&self) -> syn::Result<Attributes> {
+        let defaults = self.params.iter().filter_map(|param| {
+            let name = &param.name;
+            param
+                .default
+                .as_ref()
+                .map(|default| quote! { #name = #default })
+        });
+        syn::parse2(quote! { defaults(#(#defaults),*) })
+    }
+
+    /// The closure as a plain Rust closure, without any defaults.
+    pub fn to_closure(&self) -> TokenStream {
+        let capture = &self.capture;
+        let params = self.params.iter().map(|param| {
+            let name = &param.name;
+            match &param.ty {
+                Some(ty) => quote! { #name: #ty },
+                None => quote! { #name },
+            }
+        });
+        let body = &self.body;
+        quote! { #capture |#(#params),*| #body }
+    }
+}
+
+impl Parse for Closure {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            mutability: input.parse()?,
+            name: input.parse()?,
+            _eq_token: input.parse()?,
+            capture: input.parse()?,
+            _or1_token: input.parse()?,
+            params: parse_params(input)?,
+            _or2_token: input.parse()?,
+            body: input.parse()?,
+        })
+    }
+}
+
+fn parse_params(input: ParseStream) -> syn::Result<Punctuated<Param, syn::Token![,]>> {
+    let mut params = Punctuated::new();
+    while !input.peek(syn::Token![|]) {
+        params.push_value(input.parse()?);
+        if input.peek(syn::Token![|]) {
+            break;
+        }
+        params.push_punct(input.parse()?);
+    }
+    Ok(params)
+}
+
+impl Parse for Param {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let ty = if input.peek(syn::Token![:]) {
+            input.parse::<syn::Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let default = if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            // A default is everything up until the next parameter or the end of the parameter list. Any `|` or `,` in a
+            // default must therefore be wrapped in parentheses, braces or brackets.
+            let mut default = TokenStream::new();
+            while !input.is_empty() && !input.peek(syn::Token![,]) && !input.peek(syn::Token![|]) {
+                let tt: proc_macro2::TokenTree = input.parse()?;
+                default.extend(Some(tt));
+            }
+            if default.is_empty() {
+                return Err(input.error("expected a default value"));
+            }
+            Some(default)
+        } else {
+            None
+        };
+        Ok(Self { name, ty, default })
+    }
+}
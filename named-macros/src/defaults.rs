@@ -0,0 +1,106 @@
+use crate::arg_reconciler::ArgDetails;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit_mut::VisitMut;
+
+/// Generates a hidden struct alongside a function, with an associated function for each of its defaults.
+///
+/// Defaults are then evaluated inside these functions, where they were written, rather than being pasted into each
+/// caller, so may refer to anything which is in scope for the function itself, such as private items and the function's
+/// own generic parameters. Because the functions are generated whether or not any caller relies on them, defaults are
+/// type-checked against their parameters' types even if they're never used.
+///
+/// With `const_defaults`, defaults must also be const-evaluable. They're evaluated at compile time, unless the function
+/// has generic parameters (which a const item can't use), in which case the default functions are merely `const fn`s.
+pub fn generate_default_fns(
+    holder: &syn::Ident,
+    f: &syn::ItemFn,
+    details: &ArgDetails,
+    const_defaults: bool,
+) -> TokenStream {
+    let vis = &f.vis;
+    // Defaults of const functions must be const, so that the function can still be called in const contexts, as must all
+    // defaults with `const_defaults`.
+    let constness = if const_defaults {
+        Some(quote! { const })
+    } else {
+        f.sig.constness.map(|constness| quote! { #constness })
+    };
+    let evaluate_in_const = const_defaults && f.sig.generics.params.is_empty();
+    let (impl_generics, _ty_generics, where_clause) = f.sig.generics.split_for_impl();
+
+    let default_fns = details
+        .args
+        .iter()
+        .zip(&details.types)
+        .filter_map(|(arg, ty)| {
+            let default = details.defaults[&arg.to_string()].as_ref()?;
+            let cfg = details
+                .cfgs
+                .get(&arg.to_string())
+                .map(|cfg| quote! { #[cfg(#cfg)] });
+            // There's nothing for elided lifetimes to be elided to in the return type of a function which takes no
+            // arguments.
+            let mut ty = ty.clone();
+            ElidedLifetimes::new(syn::parse_quote! { 'static }).visit_type_mut(&mut ty);
+            let body = if evaluate_in_const {
+                quote! {
+                    const DEFAULT: #ty = #default;
+                    DEFAULT
+                }
+            } else {
+                quote! { #default }
+            };
+            Some(quote! {
+                #cfg
+                #vis #constness fn #arg #impl_generics() -> #ty #where_clause {
+                    #body
+                }
+            })
+        });
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis struct #holder {}
+
+        #[allow(dead_code, clippy::needless_lifetimes, clippy::extra_unused_type_parameters)]
+        impl #holder {
+            #(#default_fns)*
+        }
+    }
+}
+
+/// Gives any elided lifetimes in a type a named lifetime, for types which are used somewhere there's nothing for them to
+/// be elided to.
+pub struct ElidedLifetimes {
+    lifetime: syn::Lifetime,
+    /// Whether any elided lifetimes were found.
+    pub found: bool,
+}
+
+impl ElidedLifetimes {
+    pub fn new(lifetime: syn::Lifetime) -> Self {
+        Self {
+            lifetime,
+            found: false,
+        }
+    }
+}
+
+impl VisitMut for ElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.lifetime.clone());
+            self.found = true;
+        }
+        syn::visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.lifetime.clone();
+            self.found = true;
+        }
+    }
+}
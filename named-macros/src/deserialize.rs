@@ -0,0 +1,159 @@
+use crate::arg_reconciler::ArgDetails;
+use crate::args_struct::ArgsStruct;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+// Items for `#[named(serde)]`, which lets a function's arguments be deserialized from a map of argument names to values.
+//
+// Absent arguments take their defaults, which are evaluated by the same functions the macro uses. Unknown, duplicate,
+// missing and invalid arguments are reported through the `Deserializer`'s own error type, which the generated function
+// wraps in a `named::DeserializeError` naming the argument at fault.
+
+/// Generates a `Deserialize` implementation for the arguments struct, and a function which calls the function with
+/// arguments taken from any `Deserializer`.
+///
+/// The implementation is generated rather than derived so that it can record which argument it failed on, for
+/// `named::DeserializeError`.
+pub fn generate(args_struct: &ArgsStruct, details: &ArgDetails) -> TokenStream {
+    let items = details
+        .clone()
+        .cfg_variants()
+        .into_iter()
+        .map(|(gate, details)| {
+            crate::propagate::gate(&[gate], generate_variant(args_struct, &details))
+        });
+    quote! {
+        ::named::__require_serde! {
+            #(#items)*
+        }
+    }
+}
+
+fn generate_variant(args_struct: &ArgsStruct, details: &ArgDetails) -> TokenStream {
+    let serde = quote! { ::named::__private::serde };
+    let private = quote! { ::named::__private::deserialize };
+    let name = &args_struct.macro_name;
+    let from_deserializer =
+        syn::Ident::new(&format!("{}_from_deserializer", name.unraw()), name.span());
+    let args_name = &args_struct.name;
+    let visitor = syn::Ident::new(&format!("__{}Visitor", args_name), args_name.span());
+    let (_impl_generics, ty_generics, struct_where_clause) = args_struct.generics.split_for_impl();
+
+    // Everything deserializing the arguments is generic over needs each argument's type to be deserializable, and the
+    // arguments to be able to borrow from the deserializer.
+    let mut generics = args_struct.generics.clone();
+    generics.params.insert(0, syn::parse_quote! { '__de });
+    let lifetimes: Vec<_> = args_struct
+        .generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .collect();
+    let predicates = &mut generics.make_where_clause().predicates;
+    for lifetime in &lifetimes {
+        predicates.push(syn::parse_quote! { '__de: #lifetime });
+    }
+    for arg in &details.args {
+        let ty = args_struct.field_type(arg);
+        predicates.push(syn::parse_quote! { #ty: #serde::Deserialize<'__de> });
+    }
+    let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+    let mut visitor_generics = generics.clone();
+    visitor_generics
+        .params
+        .insert(0, syn::parse_quote! { '__failure });
+    let (visitor_impl_generics, _ty_generics, visitor_where_clause) =
+        visitor_generics.split_for_impl();
+    let mut visitor_struct_generics = args_struct.generics.clone();
+    visitor_struct_generics
+        .params
+        .insert(0, syn::parse_quote! { '__failure });
+    let (_impl_generics, visitor_ty_generics, _where_clause) =
+        visitor_struct_generics.split_for_impl();
+    let mut function_generics = generics.clone();
+    function_generics
+        .params
+        .push(syn::parse_quote! { __D: #serde::Deserializer<'__de> });
+    let (function_generics, _ty_generics, function_where_clause) =
+        function_generics.split_for_impl();
+
+    let args = &details.args;
+    let names: Vec<_> = args.iter().map(|arg| arg.unraw().to_string()).collect();
+    let fields = args.iter().zip(&names).map(|(arg, name)| {
+        match (&details.defaults[&arg.to_string()], &details.default_fns) {
+            (Some(_), Some(default_fns)) => {
+                quote! { #arg: #arg.unwrap_or_else(#default_fns::#arg) }
+            }
+            _ => quote! { #arg: #private::required(#arg, #name, self.failure)? },
+        }
+    });
+    let marker = args_struct.marker_init();
+    let expecting = format!("a map of the arguments of `{}!`", name.unraw());
+
+    let vis = &args_struct.vis();
+    let (asyncness, unsafety) = args_struct.qualifiers();
+    let output = &args_struct.output;
+    let call = args_struct.in_unsafety(quote! { args.call() });
+    let dot_await = args_struct.dot_await();
+    let doc = format!(
+        " Calls `{}!` with its arguments deserialized from a map of argument names to values, such as one from a configuration file.",
+        name.unraw()
+    );
+    quote! {
+        struct #visitor #visitor_struct_generics #struct_where_clause {
+            failure: &'__failure mut ::core::option::Option<#private::Failure>,
+            marker: ::core::marker::PhantomData<fn() -> #args_name #ty_generics>,
+        }
+
+        impl #visitor_impl_generics #serde::de::Visitor<'__de> for #visitor #visitor_ty_generics #visitor_where_clause {
+            type Value = #args_name #ty_generics;
+
+            fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(#expecting)
+            }
+
+            fn visit_map<__A: #serde::de::MapAccess<'__de>>(
+                self,
+                mut __map: __A,
+            ) -> ::core::result::Result<Self::Value, __A::Error> {
+                const ARGUMENTS: &[&str] = &[#(#names),*];
+                #(let mut #args = ::core::option::Option::None;)*
+                while let ::core::option::Option::Some(__key) = __map.next_key::<#private::Key>()? {
+                    match __key.as_str() {
+                        #(#names => #private::value(&mut __map, &mut #args, #names, self.failure)?,)*
+                        _ => return ::core::result::Result::Err(#private::unknown(__key, ARGUMENTS, self.failure)),
+                    }
+                }
+                ::core::result::Result::Ok(#args_name {
+                    #(#fields,)*
+                    #marker
+                })
+            }
+        }
+
+        impl #impl_generics #serde::Deserialize<'__de> for #args_name #ty_generics #where_clause {
+            fn deserialize<__D: #serde::Deserializer<'__de>>(
+                deserializer: __D,
+            ) -> ::core::result::Result<Self, __D::Error> {
+                deserializer.deserialize_map(#visitor {
+                    failure: &mut ::core::option::Option::None,
+                    marker: ::core::marker::PhantomData,
+                })
+            }
+        }
+
+        #[doc = #doc]
+        #vis #asyncness #unsafety fn #from_deserializer #function_generics(
+            deserializer: __D,
+        ) -> ::core::result::Result<#output, ::named::DeserializeError<__D::Error>> #function_where_clause {
+            let mut failure = ::core::option::Option::None;
+            let args = deserializer
+                .deserialize_map(#visitor {
+                    failure: &mut failure,
+                    marker: ::core::marker::PhantomData,
+                })
+                .map_err(|error| #private::error(failure, error))?;
+            ::core::result::Result::Ok(#call #dot_await)
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::arg_reconciler::ArgDetails;
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+
+pub fn is_doc(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("doc")
+}
+
+/// Renders the documentation for a generated macro: the documentation of whatever it calls, followed by a list of
+/// its arguments, their types, and their defaults.
+pub fn macro_docs(attrs: &[syn::Attribute], details: &ArgDetails) -> TokenStream {
+    let mut lines = Vec::new();
+    if !details.generics.is_empty() {
+        lines.push(String::new());
+        lines.push("# Generic parameters".to_owned());
+        lines.push(String::new());
+        lines.push(
+            "These may be given before any arguments, either as `<T = u32>` or as `T = u32`."
+                .to_owned(),
+        );
+        lines.push(String::new());
+        for param in &details.generics {
+            let mut line = if param.is_const {
+                format!("* `const {}`", param.name)
+            } else {
+                format!("* `{}`", param.name)
+            };
+            match &param.default {
+                Some(default) => {
+                    line.push_str(&format!(" *(default: `{}`)*", type_string(default)))
+                }
+                None => line.push_str(" *(inferred)*"),
+            }
+            lines.push(line);
+        }
+    }
+    if !details.args.is_empty() {
+        lines.push(String::new());
+        lines.push("# Arguments".to_owned());
+        lines.push(String::new());
+        for (arg, ty) in details.args.iter().zip(&details.types) {
+            let name = arg.to_string();
+            let mut line = format!("* `{}: {}`", name, type_string(ty));
+            match &details.defaults[&name] {
+                Some(default) => {
+                    line.push_str(&format!(" *(default: `{}`)*", expr_string(default)))
+                }
+                None => line.push_str(" *(required)*"),
+            }
+            let mut description = details.descriptions.get(&name).cloned().unwrap_or_default();
+            if details
+                .options
+                .as_ref()
+                .is_some_and(|options| options.name == *arg)
+            {
+                description.push_str(" Its fields may also be given as named arguments.");
+            }
+            if !description.is_empty() {
+                line.push_str(&format!(" - {}", description.trim_start()));
+            }
+            lines.push(line);
+        }
+    }
+
+    let docs = attrs.iter().filter(|attr| is_doc(attr));
+    let lines = lines
+        .iter()
+        .map(|line| format!(" {}", line).trim_end().to_owned());
+    quote! {
+        #(#docs)*
+        #(#[doc = #lines])*
+    }
+}
+
+/// Documents that a macro which calls an `unsafe fn` must itself be called from an `unsafe` block.
+pub fn unsafe_note() -> TokenStream {
+    quote! {
+        #[doc = ""]
+        #[doc = " This macro calls an `unsafe fn`, so can only be used inside an `unsafe` block, and has the same safety requirements as the function itself."]
+    }
+}
+
+/// Renders a type as it would usually be written, rather than with a space between every token.
+pub fn type_string(ty: &dyn ToTokens) -> String {
+    render(ty.to_token_stream())
+}
+
+/// Renders an expression as it would usually be written, rather than with a space between every token.
+pub fn expr_string(expr: &dyn ToTokens) -> String {
+    render(expr.to_token_stream())
+}
+
+/// Renders tokens with `tidy`, which mustn't see inside literals, as it would change what they say. Literals are hidden
+/// behind placeholder identifiers while it runs, and are then put back as they were written.
+fn render(tokens: TokenStream) -> String {
+    let mut literals = Vec::new();
+    let tidied = tidy(hide_literals(tokens, &mut literals).to_string());
+
+    let mut rendered = String::new();
+    let mut rest = tidied.as_str();
+    while let Some(start) = rest.find(LITERAL_PLACEHOLDER) {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start + LITERAL_PLACEHOLDER.len()..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let index: usize = rest[..digits].parse().unwrap();
+        rendered.push_str(&literals[index]);
+        rest = &rest[digits..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+const LITERAL_PLACEHOLDER: &str = "__named_literal_";
+
+fn hide_literals(tokens: TokenStream, literals: &mut Vec<String>) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Literal(literal) => {
+                let placeholder = format!("{}{}", LITERAL_PLACEHOLDER, literals.len());
+                literals.push(literal.to_string());
+                TokenTree::Ident(Ident::new(&placeholder, literal.span()))
+            }
+            TokenTree::Group(group) => {
+                let mut hidden =
+                    Group::new(group.delimiter(), hide_literals(group.stream(), literals));
+                hidden.set_span(group.span());
+                TokenTree::Group(hidden)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+fn tidy(mut s: String) -> String {
+    for (from, to) in &[
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" . ", "."),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        ("< ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        ("( ", "("),
+        (" (", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        ("! (", "!("),
+        ("! [", "!["),
+        (" !", "!"),
+    ] {
+        s = s.replace(from, to);
+    }
+    s.replace(" as::", " as ::")
+}
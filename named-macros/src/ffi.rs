@@ -0,0 +1,199 @@
+use crate::arg_reconciler::ArgDetails;
+use crate::args_struct::ArgsStruct;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+/// Generates the items for `#[named(ffi)]`, which let C call the function as if it had named arguments: a
+/// `foo_args_default` function which fills in the defaults of an arguments struct (which is `#[repr(C)]`), and a
+/// `foo_call` function which calls the function with one.
+///
+/// Both are exported unmangled, so that they can be declared in a header, e.g. by cbindgen, and called from C as:
+///
+/// ```c
+/// FooArgs args;
+/// foo_args_default(&args);
+/// args.required_arg = 1;
+/// foo_call(&args);
+/// ```
+///
+/// Every argument and the result must be of a type C can mirror, and the struct can't need a marker field for lifetime
+/// parameters which no argument always uses, as C callers would have to lay it out too.
+pub fn generate(
+    args_struct: &ArgsStruct,
+    f: &syn::ItemFn,
+    details: &ArgDetails,
+) -> syn::Result<TokenStream> {
+    for ty in &details.types {
+        check_ffi_safe(ty)?;
+    }
+    if let syn::ReturnType::Type(_, output) = &f.sig.output {
+        check_ffi_safe(output)?;
+    }
+    if args_struct.marker_init().is_some() {
+        return Err(syn::Error::new_spanned(
+            &f.sig.generics,
+            "`ffi` does not support lifetime parameters which no argument always uses, as the arguments struct would need a marker field for them, which C callers would have to mirror.",
+        ));
+    }
+
+    let args_name = &args_struct.name;
+    let name = args_struct.macro_name.unraw();
+    let args_default = syn::Ident::new(
+        &format!("{}_args_default", name),
+        args_struct.macro_name.span(),
+    );
+    let call_fn = syn::Ident::new(&format!("{}_call", name), args_struct.macro_name.span());
+
+    let defaults = details.args.iter().filter_map(|arg| {
+        let key = arg.to_string();
+        details.defaults[&key].as_ref()?;
+        let default_fns = details.default_fns.as_ref()?;
+        let cfg = details.cfgs.get(&key).map(|cfg| quote! { #[cfg(#cfg)] });
+        // The fields may be uninitialised, so mustn't be dropped.
+        Some(quote! {
+            #cfg
+            unsafe { ::core::ptr::addr_of_mut!((*out).#arg).write(#default_fns::#arg()) };
+        })
+    });
+
+    let vis = args_struct.vis();
+    let (impl_generics, ty_generics, where_clause) = args_struct.generics.split_for_impl();
+    let output = &args_struct.output;
+    let call = args_struct.in_unsafety(quote! { args.call() });
+    let args_default_doc = format!(
+        " Fills in the defaults of `{}!`'s defaulted arguments in `out`, leaving its other fields as they were.",
+        name
+    );
+    let call_doc = format!(" Calls `{}!` with the arguments in `args`.", name);
+    Ok(quote! {
+        #[doc = #args_default_doc]
+        ///
+        /// # Safety
+        ///
+        /// `out` must be valid for writes and properly aligned.
+        #[no_mangle]
+        #vis unsafe extern "C" fn #args_default #impl_generics(out: *mut #args_name #ty_generics) #where_clause {
+            #(#defaults)*
+        }
+
+        #[doc = #call_doc]
+        ///
+        /// # Safety
+        ///
+        /// `args` must point to a properly aligned arguments struct whose fields have all been initialised, either by the
+        /// caller or with their defaults. The arguments are moved out of it, so mustn't be used again unless they're
+        /// trivially copyable.
+        #[no_mangle]
+        #vis unsafe extern "C" fn #call_fn #impl_generics(args: *const #args_name #ty_generics) -> #output #where_clause {
+            let args = unsafe { ::core::ptr::read(args) };
+            #call
+        }
+    })
+}
+
+/// Rejects types which C can't mirror: those which are managed by Rust, have no fixed layout, or are pointers to unsized
+/// types, which are twice the size of other pointers.
+fn check_ffi_safe(ty: &syn::Type) -> syn::Result<()> {
+    let reason = match ty {
+        syn::Type::Group(group) => return check_ffi_safe(&group.elem),
+        syn::Type::Paren(paren) => return check_ffi_safe(&paren.elem),
+        syn::Type::Array(array) => return check_ffi_safe(&array.elem),
+        syn::Type::Reference(syn::TypeReference { elem, .. })
+        | syn::Type::Ptr(syn::TypePtr { elem, .. })
+            if is_unsized(elem) =>
+        {
+            Some("pointers to unsized types have no C equivalent - pass a pointer to the first element and a length, or a `*const c_char`, instead")
+        }
+        syn::Type::Slice(_) | syn::Type::TraitObject(_) => {
+            Some("unsized types can't be passed by value")
+        }
+        syn::Type::ImplTrait(_) => Some("`impl Trait` types have no fixed layout"),
+        syn::Type::Tuple(tuple) if !tuple.elems.is_empty() => {
+            Some("tuples have no fixed layout")
+        }
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().and_then(|last| {
+                match (last.ident.to_string().as_str(), type_args(last).as_slice()) {
+                    ("str", _) => Some("unsized types can't be passed by value"),
+                    ("String" | "Vec" | "VecDeque" | "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet"
+                    | "Rc" | "Arc" | "Cow" | "CString" | "OsString" | "PathBuf", _) => {
+                        Some("it's managed by Rust, so has no layout C can mirror - pass a pointer instead")
+                    }
+                    ("Box", [inner]) if is_unsized(inner) => {
+                        Some("pointers to unsized types have no C equivalent")
+                    }
+                    ("Option", [inner]) if !is_nullable_pointer(inner) => {
+                        Some("only `Option`s of references, boxes and function pointers have a layout C can mirror")
+                    }
+                    _ => None,
+                }
+            })
+        }
+        _ => None,
+    };
+    match reason {
+        Some(reason) => Err(syn::Error::new_spanned(
+            ty,
+            format!(
+                "`ffi` does not support arguments or results of type `{}`, as {}.",
+                crate::docs::type_string(ty),
+                reason
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Whether `ty` is obviously unsized, so pointers to it are twice the size of other pointers.
+fn is_unsized(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Group(group) => is_unsized(&group.elem),
+        syn::Type::Paren(paren) => is_unsized(&paren.elem),
+        syn::Type::Slice(_) | syn::Type::TraitObject(_) => true,
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().is_some_and(|last| {
+                matches!(
+                    last.ident.to_string().as_str(),
+                    "str" | "CStr" | "OsStr" | "Path"
+                )
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Whether `ty` is a pointer which is never null, so that an `Option` of it is represented as a nullable pointer.
+fn is_nullable_pointer(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Group(group) => is_nullable_pointer(&group.elem),
+        syn::Type::Paren(paren) => is_nullable_pointer(&paren.elem),
+        syn::Type::Reference(reference) => !is_unsized(&reference.elem),
+        syn::Type::BareFn(_) => true,
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().is_some_and(|last| {
+                match (last.ident.to_string().as_str(), type_args(last).as_slice()) {
+                    ("NonNull", _) => true,
+                    ("Box", [inner]) => !is_unsized(inner),
+                    _ => false,
+                }
+            })
+        }
+        _ => false,
+    }
+}
+
+/// The types a path segment is parameterised with, e.g. `T` for `Option<T>`.
+fn type_args(segment: &syn::PathSegment) -> Vec<&syn::Type> {
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
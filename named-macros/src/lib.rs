@@ -0,0 +1,1667 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::ItemFn;
+
+mod arg_reconciler;
+mod args_struct;
+mod attr_parser;
+mod call;
+mod cli;
+mod closure;
+mod defaults;
+mod deserialize;
+mod docs;
+mod ffi;
+mod propagate;
+mod register;
+mod schema;
+mod trace;
+mod wrap;
+
+/// This procedural macro allows you to produce functions which can be called with named arguments, optionally with default values. The function must be called as a macro, rather than like a "real" function.
+///
+/// > ⚠️ **Warning:** This crate is intended as an experiment to explore potential ways to provide named arguments in Rust - while it _should_ work, I wouldn't necessarily encourage its use. In particular, it has significant limitations (such as not supporting functions inside `impl` blocks), and no real intention to work around the current language restrictions in order to remove them.
+///
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(a = false, b = false))]
+/// fn or(a: bool, b: bool) -> bool {
+///     a || b
+/// }
+///
+/// fn main() {
+///     // You can use defaults for everything:
+///     assert!(!or!());
+///
+///     // Or just for some values:
+///     assert!(or!(a = true));
+///     assert!(or!(b = true));
+///     assert!(!or!(a = false));
+///     assert!(!or!(b = false));
+///
+///     // Or explicitly specify them all:
+///     assert!(or!(a = true, b = false));
+///     assert!(or!(a = false, b = true));
+///     assert!(or!(a = true, b = true));
+///     assert!(!or!(a = false, b = false));
+/// }
+/// ```
+///
+/// Arguments must be specified in the same order as they were declared in the function, so if you defined your function `fn or(a: bool, b: bool)` you couldn't call it `or!(b = true, a = true)`.
+///
+/// All arguments must be supplied with names, you can't mix and match, i.e. you can't call `or!(a = true, false)`.
+///
+/// Not all arguments need default values; you could do this:
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(b = false))]
+/// fn or(a: bool, b: bool) -> bool {
+///     a || b
+/// }
+///
+/// fn main() {
+///     assert!(or!(a = true));
+///     assert!(or!(a = true, b = true));
+/// }
+/// ```
+///
+/// Any const expression can be used as a default value:
+/// ```rust
+/// use named::named;
+///
+/// pub struct D {
+///     pub value: u8,
+/// }
+///
+/// const DEFAULT: D = D { value: 1 };
+///
+/// #[named(defaults(a = DEFAULT.value))]
+/// fn is_one(a: u8) -> bool {
+///     a == 1
+/// }
+///
+/// fn main() {
+///     assert!(is_one!());
+/// }
+/// ```
+///
+/// Defaults are evaluated where the function is defined, rather than where the macro is used, so they can use private items and the function's own generic parameters, and callers never need to import anything a default depends on. The defaults of a `const fn` are themselves evaluated in a const context, so the macro can still be used in one:
+/// ```rust
+/// #[macro_use]
+/// mod shapes {
+///     use named::named;
+///
+///     const SIDES: u32 = 4;
+///
+///     #[named(defaults(sides = SIDES))]
+///     pub const fn corners(sides: u32) -> u32 {
+///         sides
+///     }
+///
+///     #[named(defaults(value = T::default()))]
+///     pub fn boxed<T: Default>(value: T) -> Box<T> {
+///         Box::new(value)
+///     }
+/// }
+///
+/// use shapes::*;
+///
+/// const SQUARE: u32 = corners!();
+///
+/// fn main() {
+///     assert_eq!(SQUARE, 4);
+///     assert_eq!(*boxed!(T = u8), 0);
+/// }
+/// ```
+///
+/// Each default is checked against its parameter's type where it's declared, even if no caller ever relies on it. With `const_defaults`, defaults must also be const expressions, and are evaluated at compile time (or, for generic functions, in a `const fn`):
+/// ```rust
+/// use named::named;
+///
+/// const LIMIT: usize = 8;
+///
+/// #[named(const_defaults, defaults(limit = LIMIT * 2))]
+/// fn describe(limit: usize) -> String {
+///     format!("limit: {}", limit)
+/// }
+///
+/// fn main() {
+///     assert_eq!(describe!(), "limit: 16");
+/// }
+/// ```
+///
+/// If your function already takes an options struct which implements `Default`, its fields can be passed as if they were named arguments:
+/// ```rust
+/// use named::named;
+///
+/// #[derive(Default)]
+/// pub struct FetchOptions {
+///     pub retries: u8,
+///     pub verbose: bool,
+/// }
+///
+/// #[named(options = FetchOptions)]
+/// fn fetch(path: &str, options: FetchOptions) -> u8 {
+///     options.retries
+/// }
+///
+/// fn main() {
+///     assert_eq!(fetch!(path = "/", retries = 3), 3);
+///     assert_eq!(fetch!(path = "/", options = FetchOptions { retries: 2, verbose: true }), 2);
+/// }
+/// ```
+/// Any named argument which isn't an argument of the function is treated as a field of the options struct, and fields can be given in any order. Fields which aren't specified are taken from the struct's `Default` implementation, or from its default value if one is given in `defaults(...)`.
+///
+/// Values can also be taken from an existing struct whose fields have the same names as the function's arguments, much like struct update syntax. Any arguments which aren't specified are taken from the struct, rather than from their defaults:
+/// ```rust
+/// use named::named;
+///
+/// pub struct Request {
+///     pub url: &'static str,
+///     pub retries: u8,
+/// }
+///
+/// #[named(defaults(retries = 0))]
+/// fn fetch(url: &str, retries: u8) -> u8 {
+///     retries
+/// }
+///
+/// fn main() {
+///     let base = Request { url: "/", retries: 3 };
+///     assert_eq!(fetch!(..base), 3);
+///
+///     let base = Request { url: "/", retries: 3 };
+///     assert_eq!(fetch!(..base, retries = 1), 1);
+/// }
+/// ```
+/// The struct may come either first or last, and is evaluated once, where it's given. Like struct update syntax, the fields it provides are moved out of it.
+///
+/// By default, the function itself is renamed out of the way of the macro, so can only be called through the macro. `keep_name` leaves the function as it is, so that existing positional callers keep working, and it can still be used as a function pointer:
+/// ```rust
+/// use named::named;
+///
+/// #[named(keep_name, defaults(b = false))]
+/// fn or(a: bool, b: bool) -> bool {
+///     a || b
+/// }
+///
+/// fn main() {
+///     assert!(or!(a = true));
+///     assert!(or(true, false));
+///
+///     let f: fn(bool, bool) -> bool = or;
+///     assert!(f(false, true));
+/// }
+/// ```
+///
+/// The names of the generated items can also be chosen, e.g. to avoid clashing with a standard library macro such as `vec!`. `macro_name` sets the name of the macro, and `impl_name` sets the name the function itself is renamed to:
+/// ```rust
+/// use named::named;
+///
+/// #[named(macro_name = make_vec, impl_name = vec_impl, defaults(len = 2))]
+/// fn vec(len: usize, value: u8) -> Vec<u8> {
+///     std::iter::repeat(value).take(len).collect()
+/// }
+///
+/// fn main() {
+///     assert_eq!(make_vec!(value = 1), vec![1, 1]);
+///     assert_eq!(vec_impl(3, 0), vec![0, 0, 0]);
+/// }
+/// ```
+///
+/// The generated macro is documented with the function's doc comments, followed by a list of its arguments, their types, and their defaults. Each argument can be given a description with `docs`, and the renamed function and any helper macros are hidden from rustdoc:
+/// ```rust
+/// use named::named;
+///
+/// /// Greets someone.
+/// #[named(defaults(greeting = "Hello"), docs(name = "Who to greet."))]
+/// fn greet(greeting: &str, name: &str) -> String {
+///     format!("{}, {}!", greeting, name)
+/// }
+///
+/// fn main() {
+///     assert_eq!(greet!(name = "world"), "Hello, world!");
+/// }
+/// ```
+///
+/// Functions may be `const`, `unsafe`, `async`, or `extern`, and the macro behaves the same way the function would if it were called directly: a `const fn` can be called through its macro in `const` and `static` initialisers, an `async fn`'s macro evaluates to a future which can be `.await`ed, and an `unsafe fn`'s macro can only be used inside an `unsafe` block. Renaming a `#[no_mangle]` function doesn't change the symbol it's exported as.
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(b = 1))]
+/// const fn add(a: u8, b: u8) -> u8 {
+///     a + b
+/// }
+///
+/// const THREE: u8 = add!(a = 2);
+///
+/// /// # Safety
+/// ///
+/// /// `p` must be valid for reads.
+/// #[named(defaults(offset = 0))]
+/// unsafe fn read(p: *const u8, offset: usize) -> u8 {
+///     *p.add(offset)
+/// }
+///
+/// fn main() {
+///     assert_eq!(THREE, 3);
+///     assert_eq!(unsafe { read!(p = [4, 5].as_ptr()) }, 4);
+/// }
+/// ```
+///
+/// Local helpers can be given named arguments without hoisting them out into separate functions, using `named::closure!`, which binds a closure along with a macro to call it. Defaults are written inline in the closure's parameters:
+/// ```rust
+/// let offset = 10;
+/// named::closure!(add = |a: u8, b: u8 = 2| a + b + offset);
+///
+/// assert_eq!(add!(a = 1), 13);
+/// assert_eq!(add!(a = 1, b = 3), 14);
+/// ```
+///
+/// Functions can also be called through `named::call!`, which matches up arguments by name in any order, and reports every unknown, duplicated or missing argument at once, pointing at the argument responsible. Generic arguments are given in a turbofish, by name or by position, and methods of a `#[named_trait]` can be called with method syntax, where the receiver (which is auto-referenced, as in any method call) is passed as `self`. The method is qualified by its trait, as in `total.Counter::add(...)`, whatever its macro is called, so that it can't be mistaken for another trait's method or a function with the same name. Methods can also be called by their macros' names, with `self` given explicitly:
+/// ```rust
+/// use named::{named, named_trait};
+///
+/// #[named(defaults(b = 2, c = 3))]
+/// fn sum(a: u8, b: u8, c: u8) -> u8 {
+///     a + b + c
+/// }
+///
+/// #[named_trait]
+/// trait Counter {
+///     #[named(defaults(by = 1))]
+///     fn add(&mut self, by: u32) -> u32;
+/// }
+///
+/// struct Total(u32);
+///
+/// impl Counter for Total {
+///     fn add(&mut self, by: u32) -> u32 {
+///         self.0 += by;
+///         self.0
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(named::call!(sum(c = 0, a = 1)), 3);
+///
+///     let mut total = Total(0);
+///     assert_eq!(named::call!(total.Counter::add(by = 2)), 2);
+///     assert_eq!(named::call!(Counter_add(self = &mut total, by = 3)), 5);
+/// }
+/// ```
+///
+/// Arguments are always evaluated in the order they're given at the call site, along with any `..base` struct, whatever order they're passed to the function in. Values given in the order they're passed are passed straight in. Only where the expansion has to reorder them, e.g. for fields of an options struct given among the function's own arguments, for arguments given to `named::call!` out of order, or for values given before a `..base` struct, are they first bound to temporaries. This is done with a `match`, so any temporaries an argument borrows, like `&String::from("x")`, live until the call is complete. A closure which is bound like this no longer knows which parameter it's for, so may need its parameter types annotating; `named::call!` never binds closures, as creating one has no side effects. The receiver of a method call is evaluated before the other arguments, unless it's a place, like `total` or `self.total`, which the call borrows as usual.
+///
+/// A function's parameters can be inspected at runtime with `named::signature!`, which gives a constant `named::Signature` listing each parameter's name, its type and default as they're written in the function's definition, and whether it's required, e.g. for generating help text or validating configuration:
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(b = 2 * 3))]
+/// fn sum(a: u8, b: u8) -> u8 {
+///     a + b
+/// }
+///
+/// const SUM: named::Signature = named::signature!(sum);
+///
+/// fn main() {
+///     assert_eq!(SUM.params[0].name, "a");
+///     assert!(SUM.params[0].required);
+///     assert_eq!(SUM.param("b").unwrap().default, Some("2 * 3"));
+/// }
+/// ```
+///
+/// With the `serde` feature enabled, `#[named(serde)]` additionally generates a struct holding the function's arguments (named after the function, e.g. `GreetArgs` for `greet`), which can be deserialized from a map of argument names to values, and a `greet_from_deserializer` function which calls the function with arguments taken from any `serde::Deserializer`, such as one for a JSON or TOML configuration file. The same rules apply as at compile time: absent arguments take their defaults (and absent `Option` arguments without defaults are `None`, as with a derived `Deserialize`), and unknown, duplicate, missing or invalid arguments are reported as a `named::DeserializeError`, which names the argument at fault and wraps the deserializer's own error. Borrowed arguments, like `&str`, need a deserializer which can lend them, such as `serde_json::Deserializer::from_str`:
+/// ```rust
+/// use named::named;
+///
+/// #[named(serde, defaults(greeting = "Hello".to_owned()))]
+/// fn greet(name: String, greeting: String) -> String {
+///     format!("{}, {}!", greeting, name)
+/// }
+///
+/// fn main() {
+///     let config = serde_json::json!({ "name": "World" });
+///     assert_eq!(greet_from_deserializer(config).unwrap(), "Hello, World!");
+///
+///     let args: GreetArgs = serde_json::from_str(r#"{ "name": "you", "greeting": "Hi" }"#).unwrap();
+///     assert_eq!(args.call(), "Hi, you!");
+///
+///     let unknown = serde_json::json!({ "name": "World", "volume": 11 });
+///     let error = greet_from_deserializer(unknown).unwrap_err();
+///     assert_eq!(error.argument(), Some("volume"));
+/// }
+/// ```
+///
+/// With the `cli` feature enabled, `#[named(cli)]` makes the same arguments struct parseable from the command line, by implementing [clap](https://docs.rs/clap)'s `Args` for it, and generates a `greet_main` function which parses the process's command line and calls the function, and a `greet_main_from` function which does the same with a command line it's given, e.g. `greet_main_from(["greet", "--name", "World"])`. Each argument becomes a `--flag`, with underscores replaced by dashes. Arguments without defaults are required, defaulted arguments take their defaults (whose values are shown in the help text) when their flag isn't given, `Option` arguments are `None` when their flag isn't given, and `bool` arguments are switches which take no value, so can only default to `false`. Argument types are parsed with clap's `value_parser!`, so must be owned, e.g. `String` rather than `&str`, and functions with type or const parameters aren't supported. The struct can also be flattened into a larger clap command, e.g. as a subcommand:
+/// ```rust,no_run
+/// use named::named;
+///
+/// #[named(cli, docs(name = "Who to greet."), defaults(greeting = "Hello".to_owned()))]
+/// fn greet(name: String, greeting: String, shout: bool) {
+///     let greeting = format!("{}, {}!", greeting, name);
+///     println!("{}", if shout { greeting.to_uppercase() } else { greeting });
+/// }
+///
+/// fn main() {
+///     // e.g. `greet --name World --shout`
+///     greet_main();
+/// }
+/// ```
+///
+/// With the `register` feature enabled, `#[named(register)]` adds the function to the program's `named::Registry`, so that it can be called by name at runtime, such as from a scripting language or a plugin host. Functions are registered under their macros' names, and under those names' paths, e.g. `my_crate::shapes::area`; if functions in several modules share a name, calling it by that name alone returns an error listing their paths, and each can be called by its path instead. `registry.call` is checked in the same way as the function's macro is at compile time: arguments must be named after the function's parameters, parameters without defaults must be given values, and defaulted parameters which aren't given values take their defaults. Values are converted to the parameters' types, and results back, by an implementation of the `named::Value` trait, which can be written for your own dynamically typed values, and is provided for `Box<dyn Any>`, which must hold exactly the parameter's type. Registered functions can't be generic, async or unsafe, and the types of their arguments and results must be `'static`, which elided lifetimes are taken to be:
+/// ```rust
+/// use named::{args, named, Registry};
+/// use std::any::Any;
+///
+/// #[named(register, defaults(height = 1))]
+/// fn area(width: u32, height: u32) -> u32 {
+///     width * height
+/// }
+///
+/// fn main() {
+///     let registry = Registry::new();
+///     let area = registry.call::<Box<dyn Any>>("area", args! { width: 3_u32 }).unwrap();
+///     assert_eq!(area.downcast_ref::<u32>(), Some(&3));
+///     assert!(registry.call::<Box<dyn Any>>("area", args! { depth: 2_u32 }).is_err());
+/// }
+/// ```
+///
+/// `#[named(schema)]` generates a `greet_schema` function which returns a [JSON Schema](https://json-schema.org) describing the object of argument names to values which the function can be called with, such as by `#[named(serde)]`, for use by editors and validators of configuration files. Each argument is a property, described by its `docs(...)` description, and arguments without defaults are required, other than `Option` arguments, which `#[named(serde)]` takes to be `None` when they're absent. Defaults are included where they're literals, e.g. `1` or `"Hello"`, but not `"Hello".to_owned()`. Types are described by name, so primitives, strings, `Option`, and the standard library's collections and smart pointers are understood, and arguments of any other type accept any value:
+/// ```rust
+/// use named::named;
+///
+/// #[named(schema, docs(name = "Who to greet."), defaults(times = 1))]
+/// fn greet(name: String, times: u8) -> String {
+///     format!("Hello, {}!", name).repeat(times.into())
+/// }
+///
+/// fn main() {
+///     let schema: serde_json::Value = serde_json::from_str(greet_schema()).unwrap();
+///     assert_eq!(schema["properties"]["times"]["default"], 1);
+///     assert_eq!(schema["required"], serde_json::json!(["name"]));
+/// }
+/// ```
+///
+/// `#[named(ffi)]` lets C call the function with named and defaulted arguments too. It makes the arguments struct (e.g. `AreaArgs`) `#[repr(C)]`, and exports two unmangled `extern "C"` functions: `area_args_default(out: *mut AreaArgs)`, which fills in the defaults given by `defaults(...)`, leaving the other fields for the caller to set, and `area_call(args: *const AreaArgs)`, which calls the function with the struct's arguments. As they're ordinary `#[no_mangle]` items, a header declaring them can be generated with cbindgen. Argument and result types must be FFI-safe, so types managed by Rust (like `String` and `Vec`), pointers to unsized types (like `&str` and `&[T]`), tuples and most `Option`s are rejected, as are generic and async functions, and lifetime parameters which no argument uses:
+/// ```rust
+/// use named::named;
+///
+/// #[named(ffi, defaults(height = 1))]
+/// pub fn area(width: u32, height: u32) -> u64 {
+///     u64::from(width) * u64::from(height)
+/// }
+///
+/// fn main() {
+///     // In C: `AreaArgs args; area_args_default(&args); args.width = 3; area_call(&args);`
+///     let mut args = std::mem::MaybeUninit::<AreaArgs>::uninit();
+///     let area = unsafe {
+///         area_args_default(args.as_mut_ptr());
+///         (*args.as_mut_ptr()).width = 3;
+///         area_call(args.as_ptr())
+///     };
+///     assert_eq!(area, 3);
+/// }
+/// ```
+///
+/// `#[named(trace)]` records each call made through the function's macro (or `named::call!`), noting which arguments were given and which took their defaults. With the `tracing` feature enabled, each call runs in a trace-level span named after the function, with a field for each argument, and an `<argument>.defaulted = true` field for each argument which took its default. With the `log` feature enabled, each call is logged at the trace level, e.g. `greet(name = "World", greeting = "Hello" (defaulted))`. Arguments are recorded with their `Debug` implementations, or as `_` if they don't have one (including arguments whose type is a generic parameter without a `Debug` bound). Without either feature, calls aren't recorded. Const functions can't be traced:
+/// ```rust
+/// use named::named;
+///
+/// #[named(trace, defaults(greeting = "Hello"))]
+/// fn greet(name: &str, greeting: &str) -> String {
+///     format!("{}, {}!", greeting, name)
+/// }
+///
+/// fn main() {
+///     // Recorded as `greet(name = "World", greeting = "Hello" (defaulted))`.
+///     assert_eq!(greet!(name = "World"), "Hello, World!");
+/// }
+/// ```
+///
+/// Some of a function's attributes also apply to its macro. A `#[cfg(...)]` (or a `#[cfg_attr(...)]` which applies one) gates the macro along with the function, so a disabled function inside a `#[named_module]` or `#[named_trait]` doesn't leave a macro behind. A `#[deprecated]` function produces a deprecation warning wherever its macro is used. `#[must_use]` stays on the function, and still warns about unused results of the macro, which expands to a call of the function.
+///
+/// Arguments may be gated with `#[cfg(...)]`, in which case the macro only accepts them, and only uses their defaults, when they're enabled. Passing an argument which is disabled is reported as such, rather than as an unrecognised argument. Arguments may be gated on up to four distinct conditions:
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(port = 80))]
+/// fn connect(host: &str, port: u16, #[cfg(feature = "tls")] cert: &str) -> String {
+///     format!("{}:{}", host, port)
+/// }
+///
+/// fn main() {
+///     assert_eq!(connect!(host = "localhost"), "localhost:80");
+/// }
+/// ```
+///
+/// Generic arguments which can't be inferred can be given by name before any other arguments, either in angle brackets or on their own, and `types(...)` gives type and const parameters defaults, which are used whenever they aren't given explicitly. Parameters without defaults are left to be inferred:
+/// ```rust
+/// use named::named;
+/// use std::str::FromStr;
+///
+/// #[named]
+/// fn parse<T: FromStr>(input: &str) -> Option<T> {
+///     input.parse().ok()
+/// }
+///
+/// #[named(types(N = 2))]
+/// fn repeat<const N: usize>(value: u8) -> [u8; N] {
+///     [value; N]
+/// }
+///
+/// fn main() {
+///     assert_eq!(parse!(<T = u32>, input = "7"), Some(7));
+///     assert_eq!(parse!(T = i8, input = "-1"), Some(-1));
+///
+///     assert_eq!(repeat!(value = 1), [1, 1]);
+///     assert_eq!(repeat!(N = 3, value = 1), [1, 1, 1]);
+/// }
+/// ```
+///
+/// All of the smarts happen at compile time, so at runtime this macro results in plain function calls with no extra overhead.
+///
+/// Unfortunately, this can't currently be used for functions defined in `impl` blocks, e.g. those which take a `self` parameter, other than through [`macro@named_trait`]. It's possible that [postfix macros](https://github.com/rust-lang/rfcs/pull/2442) could enable this nicely.
+#[proc_macro_attribute]
+pub fn named(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut f: ItemFn = syn::parse_macro_input!(item);
+
+    match named_fn_macros(&mut f, syn::parse_macro_input::parse(attr)) {
+        Ok(macros) => {
+            let mut ts = f.into_token_stream();
+            ts.extend(macros);
+            ts.into()
+        }
+        Err(placeholder) => placeholder.into(),
+    }
+}
+
+// Renames f out of the way of the macro we'll generate for it, and generates that macro.
+// If f can't be given a macro, returns a placeholder macro with the error instead.
+fn named_fn_macros(
+    f: &mut ItemFn,
+    attr: syn::Result<attr_parser::Attributes>,
+) -> Result<TokenStream, TokenStream> {
+    let attr = attr.map_err(|err| placeholder_macro(&f.sig.ident, err))?;
+
+    // Name of the macro we'll generate - by default, the name of the original function.
+    let name = attr.macro_name().unwrap_or(&f.sig.ident).clone();
+
+    let mut details = arg_reconciler::reconcile(&f.sig.inputs, &attr)
+        .map_err(|err| placeholder_macro(&name, err))?;
+    details.generics = arg_reconciler::reconcile_generics(&f.sig.generics, &details, &attr)
+        .map_err(|err| placeholder_macro(&name, err))?;
+    if attr.cli() && !details.generics.is_empty() {
+        return Err(placeholder_macro(
+            &name,
+            syn::Error::new_spanned(
+                &f.sig.generics,
+                "`cli` does not support functions with type or const parameters, as each argument's type must be known to parse it.",
+            ),
+        ));
+    }
+    if attr.ffi() {
+        let unsupported = if !details.generics.is_empty() {
+            Some(syn::Error::new_spanned(
+                &f.sig.generics,
+                "`ffi` does not support functions with type or const parameters, as exported functions can't be generic.",
+            ))
+        } else {
+            f.sig.asyncness.map(|asyncness| {
+                syn::Error::new_spanned(asyncness, "`ffi` does not support async functions.")
+            })
+        };
+        if let Some(err) = unsupported {
+            return Err(placeholder_macro(&name, err));
+        }
+    }
+
+    // Prefix for the names of the helper macros we'll generate.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let dunder_name = syn::Ident::new(&format!("__{}", name), name.span());
+
+    let propagated = propagate::Propagated::from_attrs(&f.attrs);
+
+    let mut doc_attrs_moved = Vec::new();
+    // Macros and functions live in different namespaces, so the function can keep its name if asked to.
+    let target = match (attr.keep_name(), attr.impl_name()) {
+        (true, Some(impl_name)) => {
+            return Err(placeholder_macro(
+                &name,
+                syn::Error::new(
+                    impl_name.span(),
+                    "`impl_name` cannot be used with `keep_name`, which keeps the function's own name.",
+                ),
+            ));
+        }
+        (true, None) => f.sig.ident.clone(),
+        (false, impl_name) => {
+            // The function is an implementation detail of the macro, so its documentation moves to the macro.
+            let (doc_attrs, attrs) = f.attrs.drain(..).partition(docs::is_doc);
+            f.attrs = attrs;
+            f.attrs.push(syn::parse_quote! { #[doc(hidden)] });
+            // Similarly, its deprecation only needs reporting where the macro is used.
+            f.attrs.retain(|attr| !propagate::is_deprecated(attr));
+            doc_attrs_moved = doc_attrs;
+            // Name of the actual function we'll generate with one arg per arg of f.
+            // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+            let impl_name = impl_name.cloned().unwrap_or_else(|| {
+                syn::Ident::new(&format!("__{}", f.sig.ident), f.sig.ident.span())
+            });
+            // Renaming the function mustn't change the symbol it's exported as.
+            let symbol = f.sig.ident.unraw().to_string();
+            for attr in &mut f.attrs {
+                if attr.path.is_ident("no_mangle") {
+                    *attr = syn::parse_quote! { #[export_name = #symbol] };
+                }
+            }
+            f.sig.ident = impl_name.clone();
+            impl_name
+        }
+    };
+
+    let doc_attrs = if attr.keep_name() {
+        &f.attrs
+    } else {
+        &doc_attrs_moved
+    };
+    let docs = |details: &arg_reconciler::ArgDetails| {
+        let mut docs = docs::macro_docs(doc_attrs, details);
+        if f.sig.unsafety.is_some() {
+            docs.extend(docs::unsafe_note());
+        }
+        docs.extend(propagated.deprecated.iter().cloned());
+        docs
+    };
+
+    // Defaults are evaluated by functions defined alongside the function, so that they resolve in its scope.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let default_fns = syn::Ident::new(&format!("{}_defaults", dunder_name), name.span());
+    let mut generated =
+        defaults::generate_default_fns(&default_fns, f, &details, attr.const_defaults());
+    details.default_fns = Some(default_fns);
+    if attr.serde() || attr.cli() || attr.ffi() {
+        let args_struct = args_struct::ArgsStruct::new(&name, f, &details);
+        generated.extend(args_struct.generate(&target, &attr));
+        if attr.serde() {
+            generated.extend(deserialize::generate(&args_struct, &details));
+        }
+        if attr.cli() {
+            generated.extend(
+                cli::generate(&args_struct, &details)
+                    .map_err(|err| placeholder_macro(&name, err))?,
+            );
+        }
+        if attr.ffi() {
+            generated.extend(
+                ffi::generate(&args_struct, f, &details)
+                    .map_err(|err| placeholder_macro(&name, err))?,
+            );
+        }
+    }
+    if attr.schema() {
+        generated.extend(schema::generate(&name, &f.vis, &details));
+    }
+    if attr.register() {
+        generated.extend(
+            register::generate(&name, &target, f, &details)
+                .map_err(|err| placeholder_macro(&name, err))?,
+        );
+    }
+    // Traced calls go through a wrapper which records them.
+    let callee = if attr.trace() {
+        if let Some(constness) = f.sig.constness {
+            return Err(placeholder_macro(
+                &name,
+                syn::Error::new_spanned(
+                    constness,
+                    "`trace` does not support const functions, as calls can't be recorded at compile time.",
+                ),
+            ));
+        }
+        let wrapper = trace::wrapper_name(&dunder_name);
+        generated.extend(trace::generate(&name, &wrapper, &target, f, &details));
+        details.traced = true;
+        wrapper
+    } else {
+        target
+    };
+    generated.extend(generate_cfg_macros(
+        &name,
+        &dunder_name,
+        &callee,
+        docs,
+        details,
+    ));
+
+    Ok(propagated.gate(generated))
+}
+
+/// Produces a named-argument macro for a function you don't own, such as one from the standard library or another crate.
+///
+/// Rather than annotating the function's definition, its signature is given alongside the path to call, followed by the same arguments `#[named(...)]` accepts:
+/// ```rust
+/// named::wrap!(fn clamp = core::cmp::Ord::clamp(value: T, min: T, max: T); defaults(min = 0));
+///
+/// fn main() {
+///     assert_eq!(clamp!(value = 7, max = 5), 5);
+///     assert_eq!(clamp!(value = -2, max = 5), 0);
+/// }
+/// ```
+///
+/// Argument types are only used for documentation purposes, so don't need to name types which are in scope.
+#[proc_macro]
+pub fn wrap(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let wrap::Wrap {
+        attrs,
+        name,
+        path,
+        inputs,
+        attr,
+        ..
+    } = syn::parse_macro_input!(input);
+
+    let name = attr.macro_name().cloned().unwrap_or(name);
+
+    let details = match arg_reconciler::reconcile(&inputs, &attr) {
+        Ok(v) => v,
+        Err(err) => return placeholder_macro(&name, err).into(),
+    };
+
+    let dunder_name = syn::Ident::new(&format!("__{}", name), name.span());
+    let propagated = propagate::Propagated::from_attrs(&attrs);
+    let docs = |details: &arg_reconciler::ArgDetails| {
+        let mut docs = docs::macro_docs(&attrs, details);
+        docs.extend(propagated.deprecated.iter().cloned());
+        docs
+    };
+    propagated
+        .gate(generate_cfg_macros(
+            &name,
+            &dunder_name,
+            &path,
+            docs,
+            details,
+        ))
+        .into()
+}
+
+/// Binds a local closure, along with a local macro which calls it with named arguments.
+///
+/// Defaults are written inline in the closure's parameters, and the macro checks for required and unknown arguments just as it does for a named function:
+/// ```rust
+/// let offset = 10;
+/// named::closure!(add = |a: u8, b: u8 = 2| a + b + offset);
+///
+/// assert_eq!(add!(a = 1), 13);
+/// assert_eq!(add!(a = 1, b = 3), 14);
+///
+/// // The closure itself can still be called positionally.
+/// assert_eq!(add(1, 1), 12);
+/// ```
+///
+/// Closures which mutate their captures must be bound mutably, as in `named::closure!(mut count = |by: u32 = 1| total += by)`.
+///
+/// This must be used as a statement, and the macro can only be used after it. A default extends to the next `,` or `|`, so any default which contains either must be wrapped in parentheses.
+#[proc_macro]
+pub fn closure(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let closure: closure::Closure = syn::parse_macro_input!(input);
+    let name = &closure.name;
+
+    let details = match closure
+        .attr()
+        .and_then(|attr| arg_reconciler::reconcile(&closure.inputs(), &attr))
+    {
+        Ok(v) => v,
+        Err(err) => return placeholder_macro(name, err).into(),
+    };
+
+    let dunder_name = syn::Ident::new(&format!("__{}", name), name.span());
+    let mutability = &closure.mutability;
+    let closure_tokens = closure.to_closure();
+    let mut ts = quote! { let #mutability #name = #closure_tokens; };
+    ts.extend(call::generate_signature_macro(&dunder_name, name, &details));
+    ts.extend(generate_macros(
+        name,
+        &dunder_name,
+        name,
+        TokenStream::new(),
+        details,
+    ));
+    ts.into()
+}
+
+/// Calls a function which has a named-argument macro, matching up its arguments by name in any order.
+///
+/// The function is called as if it were an ordinary function, but with `name = value` arguments, and methods of a
+/// `#[named_trait]` can be called with method syntax, qualified by their trait, as in `receiver.Trait::method(...)`,
+/// where the receiver is passed as the argument named `self`:
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(b = 2, c = 3))]
+/// fn sum(a: u8, b: u8, c: u8) -> u8 {
+///     a + b + c
+/// }
+///
+/// fn main() {
+///     assert_eq!(named::call!(sum(c = 0, a = 1)), 3);
+/// }
+/// ```
+///
+/// Generic arguments are given in a turbofish, either by name, as in `parse::<T = u8>(...)`, or positionally. Values for
+/// unspecified arguments can be taken from a struct with `..base`, as with the function's macro.
+///
+/// Rather than reporting the first mismatch it finds, every unknown, duplicated or missing argument is reported,
+/// pointing at the argument responsible.
+#[proc_macro]
+pub fn call(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = TokenStream::from(input);
+    let call: call::Call = match syn::parse2(input.clone()) {
+        Ok(call) => call,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let signature_macro = call.signature_macro();
+    quote! { #signature_macro! { [::named::__call_with_signature] #input } }.into()
+}
+
+// Called back by a function's signature macro, with the function's signature followed by the input to `named::call!`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __call_with_signature(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let signature_and_call: call::SignatureAndCall = syn::parse_macro_input!(input);
+    match signature_and_call.expand() {
+        Ok(ts) => ts.into(),
+        // Every error is reported, so they need wrapping to be used as an expression.
+        Err(err) => {
+            let errors = err.to_compile_error();
+            quote! { { #errors unimplemented!() } }.into()
+        }
+    }
+}
+
+/// Describes the parameters of a function which has a named-argument macro, so they can be inspected at runtime.
+///
+/// Expands to a constant `named::Signature`, listing each parameter's name, type, and default in the order they're
+/// declared. Types and defaults are given as they're written in the function's definition:
+/// ```rust
+/// use named::named;
+///
+/// #[named(defaults(b = 2 * 3))]
+/// fn sum(a: u8, b: u8) -> u8 {
+///     a + b
+/// }
+///
+/// const SUM: named::Signature = named::signature!(sum);
+///
+/// fn main() {
+///     assert_eq!(SUM.name, "sum");
+///     assert_eq!(SUM.params[0].ty, "u8");
+///     assert!(SUM.params[0].required);
+///     assert_eq!(SUM.param("b").unwrap().default, Some("2 * 3"));
+/// }
+/// ```
+///
+/// Arguments which are disabled by a `#[cfg(...)]` in the current configuration aren't included.
+#[proc_macro]
+pub fn signature(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let path: syn::Path = syn::parse_macro_input!(input);
+    let signature_macro = call::signature_macro_path(&path);
+    quote! { #signature_macro! { [::named::__signature_with_signature] #path } }.into()
+}
+
+// Called back by a function's signature macro, with the function's signature followed by the input to `named::signature!`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __signature_with_signature(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let signature: call::Signature = input.parse()?;
+        let path: syn::Path = input.parse()?;
+        Ok((signature, path))
+    };
+    let (signature, path) = syn::parse_macro_input!(input with parser);
+    // Unwrap OK: a parsed path always has at least one segment.
+    signature
+        .describe(&path.segments.last().unwrap().ident)
+        .into()
+}
+
+/// Produces a named-argument macro for each method of a trait, which dispatches through the trait.
+///
+/// Each macro is named after the trait and the method, and takes the receiver as an argument named `self`, so works equally well for trait objects and generic types. Defaults are declared on each method with `#[defaults(...)]`, or with `#[named(...)]` which accepts the same arguments as `#[named(...)]` on a function:
+/// ```rust
+/// use named::named_trait;
+///
+/// #[named_trait]
+/// pub trait Greeter {
+///     #[defaults(punctuation = "!")]
+///     fn greet(&self, name: &str, punctuation: &str) -> String;
+/// }
+///
+/// struct English;
+///
+/// impl Greeter for English {
+///     fn greet(&self, name: &str, punctuation: &str) -> String {
+///         format!("Hello {}{}", name, punctuation)
+///     }
+/// }
+///
+/// fn main() {
+///     let greeter: &dyn Greeter = &English;
+///     assert_eq!(Greeter_greet!(self = greeter, name = "World"), "Hello World!");
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn named_trait(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut t: syn::ItemTrait = syn::parse_macro_input!(item);
+    if !attr.is_empty() {
+        let mut ts = syn::Error::new(
+            TokenStream::from(attr).into_iter().next().unwrap().span(),
+            "`named_trait` does not take any arguments - use `#[defaults(...)]` on each method instead.",
+        )
+        .to_compile_error();
+        ts.extend(t.into_token_stream());
+        return ts.into();
+    }
+
+    let trait_name = t.ident.clone();
+    let mut macros = TokenStream::new();
+    for item in &mut t.items {
+        if let syn::TraitItem::Method(method) = item {
+            let method_name = &method.sig.ident;
+            let attr = attr_parser::Attributes::take_from_item(&mut method.attrs);
+            // Name of the macro we'll generate for the method.
+            let name = match attr.as_ref().ok().and_then(|attr| attr.macro_name()) {
+                Some(macro_name) => macro_name.clone(),
+                None => syn::Ident::new(
+                    &format!("{}_{}", trait_name, method_name.unraw()),
+                    method_name.span(),
+                ),
+            };
+
+            let inputs = method.sig.inputs.iter().map(receiver_as_self_arg).collect();
+            let details = match attr.and_then(|attr| {
+                for (enabled, option) in &[
+                    (attr.serde(), "serde"),
+                    (attr.cli(), "cli"),
+                    (attr.ffi(), "ffi"),
+                    (attr.register(), "register"),
+                    (attr.schema(), "schema"),
+                    (attr.trace(), "trace"),
+                ] {
+                    if *enabled {
+                        return Err(syn::Error::new(
+                            method_name.span(),
+                            format!(
+                                "`{}` is only supported for functions, not trait methods.",
+                                option
+                            ),
+                        ));
+                    }
+                }
+                let mut details = arg_reconciler::reconcile(&inputs, &attr)?;
+                details.generics =
+                    arg_reconciler::reconcile_generics(&method.sig.generics, &details, &attr)?;
+                Ok(details)
+            }) {
+                Ok(v) => v,
+                Err(err) => {
+                    macros.extend(placeholder_macro(&name, err));
+                    continue;
+                }
+            };
+
+            let dunder_name = syn::Ident::new(&format!("__{}", name), name.span());
+            let target = quote! { #trait_name::#method_name };
+            let propagated = propagate::Propagated::from_attrs(&method.attrs);
+            let docs = |details: &arg_reconciler::ArgDetails| {
+                let mut docs = docs::macro_docs(&method.attrs, details);
+                if method.sig.unsafety.is_some() {
+                    docs.extend(docs::unsafe_note());
+                }
+                docs.extend(propagated.deprecated.iter().cloned());
+                docs
+            };
+            let mut generated = generate_cfg_macros(&name, &dunder_name, &target, docs, details);
+            // `named::call!` finds a method called with method syntax by its trait's name and its own, which the macro
+            // may not be named for.
+            let method_dunder_name = call::method_dunder_name(&trait_name, method_name);
+            if method_dunder_name != dunder_name {
+                generated.extend(call::generate_signature_alias(
+                    &method_dunder_name,
+                    &dunder_name,
+                ));
+            }
+            macros.extend(propagated.gate(generated));
+        }
+    }
+
+    let mut ts = t.into_token_stream();
+    ts.extend(macros);
+    ts.into()
+}
+
+/// Produces a named-argument macro for each function in an inline module which has a `#[defaults(...)]` or `#[named(...)]` attribute.
+///
+/// All of the macros are defined at the top of the module, so can be called from anywhere inside it, regardless of the order the functions are defined in. `#[named_module(all_pub)]` additionally produces macros for every `pub` function in the module, whether or not it has an attribute:
+/// ```rust
+/// use named::named_module;
+///
+/// #[named_module]
+/// mod shapes {
+///     pub fn unit_square() -> u32 {
+///         // area is defined below, but its macro can still be used here.
+///         area!(width = 1)
+///     }
+///
+///     #[defaults(height = 1)]
+///     pub fn area(width: u32, height: u32) -> u32 {
+///         width * height
+///     }
+///
+///     pub mod cubes {
+///         use super::*;
+///
+///         pub fn unit_cube() -> u32 {
+///             area!(width = 1) * 1
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(shapes::unit_square(), 1);
+///     assert_eq!(shapes::cubes::unit_cube(), 1);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn named_module(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut m: syn::ItemMod = syn::parse_macro_input!(item);
+    let attr: attr_parser::ModuleAttributes = syn::parse_macro_input!(attr);
+
+    let items = match &mut m.content {
+        Some((_brace, items)) => items,
+        None => {
+            let mut ts = syn::Error::new_spanned(
+                &m,
+                "`named_module` can only be used on modules whose contents are inline.",
+            )
+            .to_compile_error();
+            ts.extend(m.into_token_stream());
+            return ts.into();
+        }
+    };
+
+    let mut macros = TokenStream::new();
+    for item in items.iter_mut() {
+        if let syn::Item::Fn(f) = item {
+            let has_attribute = f
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("defaults") || attr.path.is_ident("named"));
+            let is_pub = matches!(f.vis, syn::Visibility::Public(_));
+            if !(has_attribute || attr.all_pub && is_pub) {
+                continue;
+            }
+            let fn_attr = attr_parser::Attributes::take_from_item(&mut f.attrs);
+            match named_fn_macros(f, fn_attr) {
+                Ok(ts) | Err(ts) => macros.extend(ts),
+            }
+        }
+    }
+
+    let macros: syn::File = syn::parse_quote! { #macros };
+    items.splice(0..0, macros.items);
+    m.into_token_stream().into()
+}
+
+// Turns a method's receiver into an ordinary argument named `self`, so that it can be passed by name.
+fn receiver_as_self_arg(arg: &syn::FnArg) -> syn::FnArg {
+    match arg {
+        syn::FnArg::Receiver(receiver) => {
+            let self_token = receiver.self_token;
+            let ty: syn::Type = match &receiver.reference {
+                Some((and, lifetime)) => {
+                    let mutability = &receiver.mutability;
+                    syn::parse_quote! { #and #lifetime #mutability Self }
+                }
+                None => syn::parse_quote! { Self },
+            };
+            syn::FnArg::Typed(syn::PatType {
+                attrs: receiver.attrs.clone(),
+                pat: Box::new(syn::Pat::Ident(syn::PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: syn::Ident::new("self", self_token.span),
+                    subpat: None,
+                })),
+                colon_token: Default::default(),
+                ty: Box::new(ty),
+            })
+        }
+        typed => typed.clone(),
+    }
+}
+
+// Create a macro, so that the only error we get is about the ill-called proc_macro,
+// and the compiler doesn't also produce errors for every call-site about the macro not existing.
+fn placeholder_macro(name: &syn::Ident, err: syn::Error) -> TokenStream {
+    let mut m = quote! { macro_rules! #name { ($($idents:ident = $exprs:expr),*) => { unimplemented!() } } };
+    m.extend(err.to_compile_error());
+    m
+}
+
+// Generates the macros for a function once for each combination of `#[cfg(...)]` conditions its arguments are gated on,
+// with each set of macros only enabled under its combination.
+fn generate_cfg_macros(
+    name: &syn::Ident,
+    dunder_name: &syn::Ident,
+    target: &dyn ToTokens,
+    docs: impl Fn(&arg_reconciler::ArgDetails) -> TokenStream,
+    details: arg_reconciler::ArgDetails,
+) -> TokenStream {
+    details
+        .cfg_variants()
+        .into_iter()
+        .map(|(gate, details)| {
+            let docs = docs(&details);
+            let mut macros = call::generate_signature_macro(dunder_name, target, &details);
+            macros.extend(generate_macros(name, dunder_name, target, docs, details));
+            propagate::gate(&[gate], macros)
+        })
+        .collect()
+}
+
+// Generates the named-argument macro `name`, which calls `target` with its arguments in declaration order.
+// Any helper macros are named with `dunder_name` as a prefix.
+fn generate_macros(
+    name: &syn::Ident,
+    dunder_name: &syn::Ident,
+    target: &dyn ToTokens,
+    docs: TokenStream,
+    mut details: arg_reconciler::ArgDetails,
+) -> TokenStream {
+    // Name of the macro which calls the function with any generic arguments, for functions with generic parameters.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let callee_name = syn::Ident::new(&format!("{}_callee", dunder_name), name.span());
+
+    // Defaults which have been given their own functions are evaluated by calling them, with the same generic arguments
+    // as the function itself.
+    if let Some(default_fns) = details.default_fns.take() {
+        for arg in &details.args {
+            if let Some(default) = details
+                .defaults
+                .get_mut(&arg.to_string())
+                .and_then(Option::as_mut)
+            {
+                *default = if details.generics.is_empty() {
+                    quote! { #default_fns::#arg() }
+                } else {
+                    quote! { #callee_name!(@with [#default_fns::#arg]) }
+                };
+            }
+        }
+    }
+
+    // With generic parameters, the public macro first picks out any generic arguments given explicitly, then hands over
+    // to the macros which handle the arguments, which call the function through a macro which supplies them.
+    if !details.generics.is_empty() {
+        let generics = std::mem::take(&mut details.generics);
+        // Names of the macros which collect generic arguments, and which take the function's arguments.
+        // These are considered private implementation details, and should not be relied on - they may change or be removed in a patch release.
+        let generics_name = syn::Ident::new(&format!("{}_generics", dunder_name), name.span());
+        let args_name = syn::Ident::new(&format!("{}_args", dunder_name), name.span());
+        let mut ts = generate_generics_macro(
+            name,
+            docs,
+            &generics_name,
+            &args_name,
+            &callee_name,
+            target,
+            &generics,
+        );
+        ts.extend(generate_macros(
+            &args_name,
+            dunder_name,
+            &quote! { #callee_name! },
+            quote! { #[doc(hidden)] },
+            details,
+        ));
+        return ts;
+    }
+
+    let arg_reconciler::ArgDetails {
+        args,
+        defaults,
+        options,
+        disabled,
+        traced,
+        ..
+    } = &details;
+    let traced = *traced;
+
+    // Name of the inner macro we'll generate which accumulates non-named arguments from the front.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let inner_name = syn::Ident::new(&format!("{}_inner", dunder_name), name.span());
+    // Name of the macro which only expects name-value pairs for the function's own arguments.
+    // If the function takes an options struct, this sits behind a macro which first separates out the struct's fields.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let entry_name = if options.is_some() {
+        syn::Ident::new(&format!("{}_named", dunder_name), name.span())
+    } else {
+        name.clone()
+    };
+    // Name of the macro which fills in any unspecified arguments from a spread struct, e.g. `foo!(..base, a = 1)`.
+    // This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+    let spread_name = syn::Ident::new(&format!("{}_spread", dunder_name), name.span());
+    let spread_start = args.first().map(|arg| quote! { @#arg });
+
+    let mut ts = TokenStream::new();
+
+    // Generate the inner macro, which walks the function's arguments in order, taking each from the front of the named
+    // values if it's next, or from its default if not. Each argument only needs a fixed number of branches, so the size
+    // of the macro, and the depth of its recursion, grow linearly with the number of arguments.
+    {
+        // Values for arguments which have been configured away are reported as such, wherever they appear.
+        let mut branches = disabled_branches(disabled, quote! { $(@$state:ident)? [$($out:tt)*] });
+        for (index, arg) in args.iter().enumerate() {
+            // The state after this argument - once every argument has been taken, the state is empty.
+            let next = args.get(index + 1).map(|next| quote! { @#next });
+            let default = defaults[&arg.to_string()]
+                .clone()
+                .map(|default| trace::defaulted(traced, default));
+            let value = trace::given(traced, quote! { $value });
+
+            branches.push(quote! {
+                (@#arg [$($out:tt)*] #arg = $value:expr $(, $($rest:tt)*)?) => {
+                    #inner_name!(#next [$($out)* #value,] $($($rest)*)?)
+                };
+            });
+
+            // Nothing more was given, so every remaining argument is missing.
+            let rhs = {
+                let missing_required: Vec<String> = defaults
+                    .iter()
+                    .skip(index)
+                    .filter(|(_k, v)| v.is_none())
+                    .map(|(k, _v)| k.clone())
+                    .collect();
+                if missing_required.is_empty() {
+                    quote! { #inner_name!(#next [$($out)* #default,]) }
+                } else {
+                    report_missing(&missing_required)
+                }
+            };
+            branches.push(quote! { (@#arg [$($out:tt)*]) => { #rhs }; });
+
+            // Something else was given, so this argument is missing.
+            let rhs = match default {
+                Some(default) => quote! { #inner_name!(#next [$($out)* #default,] $($rest)+) },
+                // TODO: Would ideally specify all missing, not just next.
+                None => report_missing(&[arg.to_string()]),
+            };
+            branches.push(quote! { (@#arg [$($out:tt)*] $($rest:tt)+) => { #rhs }; });
+        }
+
+        let expected_names = format_names(&args.iter().map(|v| v.to_string()).collect::<Vec<_>>());
+        branches.push(quote! {
+            ([$($out:tt)*]) => { #target($($out)*) };
+            // All args given, yet we have more!
+            // TODO: Maybe mention all, not just first.
+            ([$($out:tt)*] $ident:ident = $expr:expr $(, $($rest:tt)*)?) => {
+                compile_error!(concat!("Unrecognized named argument - got value for argument `", stringify!($ident), "` but only expected ", #expected_names))
+            };
+        });
+
+        ts.extend(quote! { #[doc(hidden)] macro_rules! #inner_name { #(#branches)* } });
+    }
+
+    // Generate the actual named-values macro, which only expects name-value pairs.
+    // The entry macro is only public if there's no options struct to sort the named values first.
+    let (entry_docs, public_docs) = if options.is_none() {
+        (docs, TokenStream::new())
+    } else {
+        (quote! { #[doc(hidden)] }, docs)
+    };
+    {
+        let mut branches = Vec::with_capacity(3);
+        if options.is_none() {
+            branches.extend(spread_branches(|base, args| {
+                quote! { #spread_name!(#spread_start #base [] #args) }
+            }));
+        }
+        branches.push(quote! { ($($args:tt)*) => { #inner_name!(#spread_start [] $($args)*) }; });
+
+        ts.extend(quote! {
+            // foo hands its name-value pairs to the inner macro, starting from the first argument.
+            // It is not allowed any bare :exprs.
+            #entry_docs
+            macro_rules! #entry_name {
+                #(#branches)*
+            }
+        });
+    }
+
+    ts.extend(generate_spread_macro(
+        &spread_name,
+        &inner_name,
+        args,
+        traced,
+    ));
+
+    if options.is_some() {
+        let options_name = syn::Ident::new(&format!("{}_options", dunder_name), name.span());
+        ts.extend(generate_options_macro(
+            name,
+            public_docs,
+            &options_name,
+            &entry_name,
+            &spread_name,
+            &details,
+        ));
+    }
+    ts
+}
+
+// Generates the public macro for a function with generic parameters.
+//
+// Generic arguments given explicitly, either as `<T = u32>` or as `T = u32`, are collected into one slot per parameter,
+// each of which starts off holding the parameter's default, or `_` to leave it to be inferred. The remaining arguments
+// are then passed on, alongside a macro which calls the function with the collected generic arguments. As a macro can't
+// otherwise generate another macro with its own metavariables, the `$` which that needs is passed along as `($d)`.
+fn generate_generics_macro(
+    name: &syn::Ident,
+    docs: TokenStream,
+    generics_name: &syn::Ident,
+    args_name: &syn::Ident,
+    callee_name: &syn::Ident,
+    target: &dyn ToTokens,
+    generics: &[arg_reconciler::GenericParam],
+) -> TokenStream {
+    let metavars: Vec<_> = generics.iter().map(|param| metavar(&param.name)).collect();
+    let matchers = quote! { #([$($#metavars:tt)*])* };
+    let values = quote! { #([$($#metavars)*])* };
+    // The slots with the one at `index` replaced with `value`.
+    let replaced = |index: usize, value: TokenStream| {
+        let slots = metavars.iter().enumerate().map(|(i, metavar)| {
+            if i == index {
+                quote! { [#value] }
+            } else {
+                quote! { [$($#metavar)*] }
+            }
+        });
+        quote! { #(#slots)* }
+    };
+
+    let mut branches = Vec::new();
+    for (index, param) in generics.iter().enumerate() {
+        let param_name = &param.name;
+        // Const arguments in a turbofish need braces unless they're a literal or a single identifier, so always add them.
+        let (value, bare_fragments, bracketed_fragments) = if param.is_const {
+            (
+                quote! { { $generic } },
+                vec![quote! { expr }],
+                vec![quote! { literal }, quote! { tt }],
+            )
+        } else {
+            (
+                quote! { $generic },
+                vec![quote! { ty }],
+                vec![quote! { ty }],
+            )
+        };
+        let replaced = replaced(index, value);
+        for fragment in bare_fragments {
+            branches.push(quote! {
+                (@generics ($d:tt) #matchers #param_name = $generic:#fragment $(, $($rest:tt)*)?) => {
+                    #generics_name!(@generics ($d) #replaced $($($rest)*)?)
+                };
+            });
+        }
+        for fragment in bracketed_fragments {
+            branches.push(quote! {
+                (@bracketed ($d:tt) #matchers #param_name = $generic:#fragment , $($rest:tt)*) => {
+                    #generics_name!(@bracketed ($d) #replaced $($rest)*)
+                };
+                (@bracketed ($d:tt) #matchers #param_name = $generic:#fragment > $(, $($rest:tt)*)?) => {
+                    #generics_name!(@generics ($d) #replaced $($($rest)*)?)
+                };
+            });
+        }
+    }
+
+    let turbofish: Vec<_> = metavars
+        .iter()
+        .map(|metavar| quote! { $($#metavar)* })
+        .collect();
+    branches.push(quote! {
+        (@generics ($d:tt) #matchers < $($rest:tt)*) => {
+            #generics_name!(@bracketed ($d) #values $($rest)*)
+        };
+        (@bracketed ($d:tt) #matchers > $(, $($rest:tt)*)?) => {
+            #generics_name!(@generics ($d) #values $($($rest)*)?)
+        };
+        (@generics ($d:tt) #matchers $($rest:tt)*) => {{
+            macro_rules! #callee_name {
+                (@with [$d($d path:tt)*]) => { $d($d path)*::<#(#turbofish),*>() };
+                ($d($d args:tt)*) => { #target::<#(#turbofish),*>($d($d args)*) };
+            }
+            #args_name!($($rest)*)
+        }};
+    });
+
+    let initial = generics
+        .iter()
+        .map(|param| match (&param.default, param.is_const) {
+            (Some(default), true) => quote! { [{ #default }] },
+            (Some(default), false) => quote! { [#default] },
+            (None, _) => quote! { [_] },
+        });
+
+    quote! {
+        #[doc(hidden)]
+        macro_rules! #generics_name {
+            #(#branches)*
+        }
+
+        #docs
+        macro_rules! #name {
+            ($($args:tt)*) => { #generics_name!(@generics ($) #(#initial)* $($args)*) };
+        }
+    }
+}
+
+// Generates branches which reject values for arguments which don't exist under the current configuration, explaining
+// why, rather than reporting them as unrecognised.
+fn disabled_branches(
+    disabled: &[(syn::Ident, TokenStream)],
+    prefix: TokenStream,
+) -> Vec<TokenStream> {
+    disabled
+        .iter()
+        .map(|(arg, cfg)| {
+            let message = disabled_message(arg, cfg);
+            quote! { (#prefix #arg = $value:expr $(, $($rest:tt)*)?) => { compile_error!(#message) }; }
+        })
+        .collect()
+}
+
+fn disabled_message(arg: &syn::Ident, cfg: &TokenStream) -> String {
+    format!(
+        "Argument `{}` is not available - it only exists when `cfg({})` is enabled",
+        arg,
+        docs::expr_string(cfg),
+    )
+}
+
+// Generates the branches which accept a spread struct, either before or after the named values.
+//
+// The spread struct is evaluated once, and bound to a local whose name is passed on to the macros which
+// generate the call, as if it were a macro-local identifier, as `{name}`. A spread struct given after the named values
+// is only evaluated after them, so is passed on as `{name = value}` for the spread macro to bind.
+fn spread_branches(start: impl Fn(TokenStream, TokenStream) -> TokenStream) -> Vec<TokenStream> {
+    let base = quote! { __named_base };
+    let leading = start(quote! { {#base} }, quote! { $($($args)*)? });
+    let trailing = start(quote! { {#base = $base} }, quote! { $($keys = $values),+ });
+    vec![
+        quote! { (..$base:expr $(, $($args:tt)*)?) => { { let #base = $base; #leading } }; },
+        quote! { ($($keys:ident = $values:expr),+ , ..$base:expr $(,)?) => { #trailing }; },
+    ]
+}
+
+// Generates the macro which fills in unspecified arguments from a spread struct.
+//
+// Arguments are walked in order; each one is either taken from the named values, or from the field of the spread
+// struct with the same name. The complete set of values is then passed to the inner macro, along with any named values
+// left over, so that it can report them.
+//
+// A spread struct which comes after the named values is evaluated after them too, so in that case, and only then, named
+// values are bound to temporaries as they're walked.
+fn generate_spread_macro(
+    spread_name: &syn::Ident,
+    inner_name: &syn::Ident,
+    args: &[syn::Ident],
+    traced: bool,
+) -> TokenStream {
+    let mut branches = Vec::with_capacity(3 * args.len() + 2);
+    for (index, arg) in args.iter().enumerate() {
+        let next = args.get(index + 1).map(|next| quote! { @#next });
+        // No struct can have a field named `self`, so a receiver must always be given explicitly.
+        let from_base = if arg == "self" {
+            report_missing(&[arg.to_string()])
+        } else {
+            let value = trace::given(traced, quote! { $base.#arg });
+            quote! { #spread_name!(#next {$base $(= $base_value)?} [$($out)* #value,] $($rest)*) }
+        };
+        let value = trace::given(traced, quote! { $value });
+        let bound_value = trace::given(traced, quote! { __named_value });
+        branches.push(quote! {
+            (@#arg {$base:ident} [$($out:tt)*] #arg = $value:expr $(, $($rest:tt)*)?) => {
+                #spread_name!(#next {$base} [$($out)* #value,] $($($rest)*)?)
+            };
+            (@#arg {$base:ident = $base_value:expr} [$($out:tt)*] #arg = $value:expr $(, $($rest:tt)*)?) => {
+                match $value {
+                    __named_value => #spread_name!(#next {$base = $base_value} [$($out)* #bound_value,] $($($rest)*)?),
+                }
+            };
+            (@#arg {$base:ident $(= $base_value:expr)?} [$($out:tt)*] $($rest:tt)*) => {
+                #from_base
+            };
+        });
+    }
+    branches.push(quote! {
+        ({$base:ident} [$($out:tt)*] $($rest:tt)*) => { #inner_name!([$($out)*] $($rest)*) };
+        ({$base:ident = $base_value:expr} [$($out:tt)*] $($rest:tt)*) => {{
+            let $base = $base_value;
+            #inner_name!([$($out)*] $($rest)*)
+        }};
+    });
+
+    quote! { #[doc(hidden)] macro_rules! #spread_name { #(#branches)* } }
+}
+
+// Generates the public macro for a function which takes an options struct.
+//
+// Arguments are sorted into three buckets: function arguments which come before the options struct, function
+// arguments which come after it, and anything else, which is assumed to be a field of the options struct.
+// The options struct is then constructed from its fields and passed to the entry macro as if it were a
+// regularly named argument.
+//
+// Each value is paired with a temporary as it's sorted. If sorting reorders the values, i.e. a field comes before a
+// function argument which is passed ahead of the options struct, or after one which is passed behind it, or if a spread
+// struct comes after the values, each value is bound to its temporary, in the order they were given, before the call.
+// Otherwise the values are passed straight in, so that closures can still infer their parameter types from the
+// function's. The phase records which buckets have been sorted into so far, and whether they've been reordered.
+fn generate_options_macro(
+    name: &syn::Ident,
+    docs: TokenStream,
+    options_name: &syn::Ident,
+    entry_name: &syn::Ident,
+    spread_name: &syn::Ident,
+    details: &arg_reconciler::ArgDetails,
+) -> TokenStream {
+    let arg_reconciler::ArgDetails {
+        args,
+        defaults,
+        options,
+        disabled,
+        ..
+    } = details;
+    // Unwrap OK: only called for functions which take an options struct.
+    let options = options.as_ref().unwrap();
+    let options_arg = &options.name;
+    let options_path = &options.path;
+    // Unwrap OK: reconcile always gives the options struct a default.
+    let options_default = defaults[&options_arg.to_string()].clone().unwrap();
+
+    let options_index = args.iter().position(|arg| arg == options_arg).unwrap();
+    let before = &args[..options_index];
+    let spread_start = args.first().map(|arg| quote! { @#arg });
+    let after = &args[options_index + 1..];
+
+    let conflict = format!(
+        "Cannot specify fields of `{}` when also specifying `{}`",
+        options_path.to_token_stream(),
+        options_arg,
+    );
+
+    // The values sorted so far, each as `(temporary, value)`, and the values in the order they were given.
+    let lists =
+        quote! { [$($before:tt)*] [$($after:tt)*] [$($fields:tt)*] {$($base:tt)*} [$($bound:tt)*] };
+    let rest = quote! { $(, $($rest:tt)*)? };
+
+    // Makes the call, once the values are ready to be passed in.
+    let mut branches = vec![quote! {
+        (@call [$($before:tt)*] [$($after:tt)*] [] {}) => {
+            #entry_name!($($before)* $($after)*)
+        };
+        (@call [$($before:tt)*] [$($after:tt)*] [] {$base:ident $(= $base_value:expr)?}) => {{
+            $(let $base = $base_value;)?
+            #spread_name!(#spread_start {$base} [] $($before)* $($after)*)
+        }};
+        (@call [$($before:tt)*] [$($after:tt)*] [$($fields:tt)+] {}) => {
+            #entry_name!($($before)* #options_arg = #options_path { $($fields)* ..#options_default }, $($after)*)
+        };
+        (@call [$($before:tt)*] [$($after:tt)*] [$($fields:tt)+] {$base:ident $(= $base_value:expr)?}) => {{
+            $(let $base = $base_value;)?
+            #spread_name!(#spread_start {$base} [] $($before)* #options_arg = #options_path { $($fields)* ..$base.#options_arg }, $($after)*)
+        }};
+    }];
+    branches.extend(disabled_branches(
+        disabled,
+        quote! { @$phase:ident {$($reordered:tt)*} #lists },
+    ));
+    for arg in before {
+        branches.push(quote! {
+            (@before {$($reordered:tt)*} #lists #arg = $value:expr #rest) => {
+                #options_name!(@before {$($reordered)*} [$($before)* #arg = (__named_value, $value),] [$($after)*] [$($fields)*] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+            };
+            (@fields {$($reordered:tt)*} #lists #arg = $value:expr #rest) => {
+                #options_name!(@fields {reordered} [$($before)* #arg = (__named_value, $value),] [$($after)*] [$($fields)*] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+            };
+        });
+    }
+    for arg in after {
+        branches.push(quote! {
+            (@before {$($reordered:tt)*} #lists #arg = $value:expr #rest) => {
+                #options_name!(@after {$($reordered)*} [$($before)*] [$($after)* #arg = (__named_value, $value),] [$($fields)*] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+            };
+            (@fields {$($reordered:tt)*} #lists #arg = $value:expr #rest) => {
+                #options_name!(@after {$($reordered)*} [$($before)*] [$($after)* #arg = (__named_value, $value),] [$($fields)*] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+            };
+        });
+    }
+    branches.push(quote! {
+        (@$phase:ident {$($reordered:tt)*} [$($before:tt)*] [$($after:tt)*] [] {$($base:tt)*} [$($bound:tt)*] #options_arg = $value:expr #rest) => {
+            #options_name!(@explicit {$($reordered)*} [$($before)*] [$($after)* #options_arg = (__named_value, $value),] [] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+        };
+        (@$phase:ident {$($reordered:tt)*} [$($before:tt)*] [$($after:tt)*] [$($fields:tt)+] {$($base:tt)*} [$($bound:tt)*] #options_arg = $value:expr #rest) => {
+            compile_error!(#conflict)
+        };
+    });
+    for arg in before.iter().chain(after) {
+        branches.push(quote! {
+            (@$phase:ident {$($reordered:tt)*} #lists #arg = $value:expr #rest) => {
+                #options_name!(@$phase {$($reordered)*} [$($before)*] [$($after)* #arg = (__named_value, $value),] [$($fields)*] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+            };
+        });
+    }
+    branches.push(quote! {
+        (@explicit {$($reordered:tt)*} [$($before:tt)*] [$($after:tt)*] [] {$($base:tt)*} [$($bound:tt)*] $field:ident = $value:expr #rest) => {
+            compile_error!(#conflict)
+        };
+        (@after {$($reordered:tt)*} #lists $field:ident = $value:expr #rest) => {
+            #options_name!(@after {reordered} [$($before)*] [$($after)*] [$($fields)* $field: (__named_value, $value),] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+        };
+        (@$phase:ident {$($reordered:tt)*} #lists $field:ident = $value:expr #rest) => {
+            #options_name!(@fields {$($reordered)*} [$($before)*] [$($after)*] [$($fields)* $field: (__named_value, $value),] {$($base)*} [$($bound)* (__named_value, $value),] $($($rest)*)?)
+        };
+    });
+
+    // Once everything has been sorted, the values are either passed straight in, or bound first if they were reordered.
+    branches.push(quote! {
+        (@$phase:ident {}
+            [$($before_arg:ident = ($before_temporary:ident, $before_value:expr),)*]
+            [$($after_arg:ident = ($after_temporary:ident, $after_value:expr),)*]
+            [$($field:ident: ($field_temporary:ident, $field_value:expr),)*]
+            {$($base:ident)?} [$($bound:tt)*]
+        ) => {
+            #options_name!(@call
+                [$($before_arg = $before_value,)*]
+                [$($after_arg = $after_value,)*]
+                [$($field: $field_value,)*]
+                {$($base)?}
+            )
+        };
+        (@$phase:ident {$($reordered:tt)*}
+            [$($before_arg:ident = ($before_temporary:ident, $before_value:expr),)*]
+            [$($after_arg:ident = ($after_temporary:ident, $after_value:expr),)*]
+            [$($field:ident: ($field_temporary:ident, $field_value:expr),)*]
+            {$($base:tt)*} [$(($temporary:ident, $value:expr),)*]
+        ) => {
+            match ($($value,)*) {
+                ($($temporary,)*) => #options_name!(@call
+                    [$($before_arg = $before_temporary,)*]
+                    [$($after_arg = $after_temporary,)*]
+                    [$($field: $field_temporary,)*]
+                    {$($base)*}
+                ),
+            }
+        };
+    });
+
+    let public_branches = spread_branches(|base, args| {
+        quote! { #options_name!(@before {} [] [] [] #base [] #args) }
+    });
+
+    quote! {
+        #[doc(hidden)]
+        macro_rules! #options_name {
+            #(#branches)*
+        }
+
+        // foo sorts its named values into function arguments and fields of the options struct.
+        #docs
+        macro_rules! #name {
+            #(#public_branches)*
+            ($($args:tt)*) => { #options_name!(@before {} [] [] [] {} [] $($args)*) };
+        }
+    }
+}
+
+// Name of the macro variable which holds the value of an argument.
+// Prefixed so that it can't clash with the names of any of the variables the generated macros use themselves.
+fn metavar(arg: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&format!("__{}", arg.unraw()), arg.span())
+}
+
+fn report_missing(missing: &[String]) -> TokenStream {
+    let maybe_s = if missing.len() == 1 { "" } else { "s" };
+    let missing_str = format!(
+        "Must specify value{} for non-defaulted argument{}: {}",
+        maybe_s,
+        maybe_s,
+        format_names(missing),
+    );
+    quote! { compile_error!(#missing_str) }
+}
+
+fn format_names(names: &[String]) -> String {
+    if names.len() == 1 {
+        format!("`{}`", names[0])
+    } else {
+        format!("[{}]", names.join(", "))
+    }
+}
@@ -0,0 +1,70 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Attributes of a function which also apply to the macros generated for it.
+#[derive(Default)]
+pub struct Propagated {
+    /// `cfg`s (and `cfg_attr`s which apply `cfg`s), which gate every generated macro.
+    pub cfgs: Vec<TokenStream>,
+    /// `deprecated` (and `cfg_attr`s which apply `deprecated`), which only apply to the public macro, as that's the
+    /// one which callers use.
+    pub deprecated: Vec<TokenStream>,
+}
+
+impl Propagated {
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> Self {
+        let mut propagated = Self::default();
+        for attr in attrs {
+            if attr.path.is_ident("cfg") {
+                propagated.cfgs.push(quote! { #attr });
+            } else if is_deprecated(attr) {
+                propagated.deprecated.push(quote! { #attr });
+            } else if attr.path.is_ident("cfg_attr") {
+                // Anything which doesn't look like a well-formed `cfg_attr` is left for the compiler to complain about.
+                if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
+                    let mut nested = list.nested.iter();
+                    let predicate = match nested.next() {
+                        Some(predicate) => predicate,
+                        None => continue,
+                    };
+                    let is = |meta: &&syn::NestedMeta, name: &str| matches!(meta, syn::NestedMeta::Meta(meta) if meta.path().is_ident(name));
+                    let cfgs: Vec<_> = nested.clone().filter(|meta| is(meta, "cfg")).collect();
+                    let deprecated: Vec<_> = nested.filter(|meta| is(meta, "deprecated")).collect();
+                    if !cfgs.is_empty() {
+                        propagated
+                            .cfgs
+                            .push(quote! { #[cfg_attr(#predicate, #(#cfgs),*)] });
+                    }
+                    if !deprecated.is_empty() {
+                        propagated
+                            .deprecated
+                            .push(quote! { #[cfg_attr(#predicate, #(#deprecated),*)] });
+                    }
+                }
+            }
+        }
+        propagated
+    }
+
+    /// Gates each of the macros in `macros` behind the propagated `cfg`s.
+    pub fn gate(&self, macros: TokenStream) -> TokenStream {
+        gate(&self.cfgs, macros)
+    }
+}
+
+/// Puts each of the macros in `macros` behind all of the `cfgs`.
+pub fn gate(cfgs: &[TokenStream], macros: TokenStream) -> TokenStream {
+    if cfgs.iter().all(TokenStream::is_empty) {
+        return macros;
+    }
+    // Unwrap OK: the macros we generate are always a sequence of `macro_rules!` items.
+    let file: syn::File = syn::parse2(macros).unwrap();
+    file.items
+        .iter()
+        .map(|item| quote! { #(#cfgs)* #item })
+        .collect()
+}
+
+pub fn is_deprecated(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("deprecated")
+}
@@ -0,0 +1,117 @@
+use crate::arg_reconciler::ArgDetails;
+use crate::defaults::ElidedLifetimes;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::visit_mut::VisitMut;
+
+/// Generates the items for `#[named(register)]`: a `named::Function` describing the function, and how to call it with
+/// type-erased arguments, which is submitted to the `named::Registry` of whichever program it's linked into.
+///
+/// Arguments and results are passed as `Box<dyn Any>`, so the function can't be generic, and its arguments' types must
+/// be `'static`, which any elided lifetimes in them are taken to be.
+pub fn generate(
+    name: &syn::Ident,
+    target: &syn::Ident,
+    f: &syn::ItemFn,
+    details: &ArgDetails,
+) -> syn::Result<TokenStream> {
+    for (qualifier, present) in &[
+        ("async", f.sig.asyncness.is_some()),
+        ("unsafe", f.sig.unsafety.is_some()),
+    ] {
+        if *present {
+            return Err(syn::Error::new_spanned(
+                &f.sig,
+                format!("`register` does not support {} functions.", qualifier),
+            ));
+        }
+    }
+    if !f.sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &f.sig.generics,
+            "`register` does not support functions with generic parameters, as each argument's type must be known to pass it at runtime.",
+        ));
+    }
+
+    let any = quote! { ::std::boxed::Box<dyn ::core::any::Any> };
+    let output = match &f.sig.output {
+        syn::ReturnType::Default => syn::parse_quote! { () },
+        syn::ReturnType::Type(_, ty) => ty.as_ref().clone(),
+    };
+    let output = describe_type(&output);
+
+    let submissions = details
+        .clone()
+        .cfg_variants()
+        .into_iter()
+        .map(|(gate, details)| {
+            let signature = crate::call::describe(
+                name,
+                details.args.iter().zip(&details.types).map(|(arg, ty)| {
+                    (
+                        arg,
+                        ty as &dyn ToTokens,
+                        details.defaults[&arg.to_string()].as_ref(),
+                    )
+                }),
+            );
+            let types: Vec<_> = details.types.iter().map(describe_type).collect();
+            let defaults = details.args.iter().map(|arg| {
+                match (&details.defaults[&arg.to_string()], &details.default_fns) {
+                    (Some(_), Some(default_fns)) => quote! {
+                        {
+                            fn default() -> #any {
+                                ::std::boxed::Box::new(#default_fns::#arg())
+                            }
+                            ::core::option::Option::Some(default as fn() -> #any)
+                        }
+                    },
+                    _ => quote! { ::core::option::Option::None },
+                }
+            });
+            let function = name.unraw().to_string();
+            let args = details.args.iter().zip(&types).map(|(arg, (_, ty))| {
+                let argument = arg.unraw().to_string();
+                quote! {
+                    ::named::__private::registry::take_arg::<#ty>(&mut args, #function, #argument)?
+                }
+            });
+            let types = types.iter().map(|(described, _)| described);
+            let (described_output, _) = &output;
+            let submission = quote! {
+                ::named::__private::inventory::submit! {
+                    ::named::Function {
+                        signature: #signature,
+                        module: ::core::module_path!(),
+                        types: &[#(#types),*],
+                        output: #described_output,
+                        defaults: &[#(#defaults),*],
+                        call: {
+                            fn call(
+                                args: ::std::vec::Vec<#any>,
+                            ) -> ::core::result::Result<#any, ::named::CallError> {
+                                let mut args = args.into_iter();
+                                ::core::result::Result::Ok(::std::boxed::Box::new(#target(#(#args),*)))
+                            }
+                            call
+                        },
+                    }
+                }
+            };
+            crate::propagate::gate(&[gate], submission)
+        });
+    Ok(quote! {
+        ::named::__require_register! {
+            #(#submissions)*
+        }
+    })
+}
+
+/// A `named::Type` for `ty` as a constant expression, along with `ty` itself with any elided lifetimes made `'static`.
+fn describe_type(ty: &syn::Type) -> (TokenStream, syn::Type) {
+    let mut ty = ty.clone();
+    ElidedLifetimes::new(syn::parse_quote! { 'static }).visit_type_mut(&mut ty);
+    let name = crate::docs::type_string(&ty);
+    (quote! { ::named::Type::new::<#ty>(#name) }, ty)
+}
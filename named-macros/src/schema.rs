@@ -0,0 +1,322 @@
+use crate::arg_reconciler::ArgDetails;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+
+/// Generates the items for `#[named(schema)]`: a `foo_schema` function which returns a JSON Schema describing the
+/// object of argument names to values which the function can be called with, e.g. by `#[named(serde)]`.
+///
+/// The schema is rendered when the function is compiled, from the arguments' types as they're written, so only types
+/// which are recognised by name (primitives, strings, and the standard library's collections and smart pointers) are
+/// described; any other type allows any value. Arguments without defaults are required, other than `Option`s, and
+/// defaults are included where they're literals.
+pub fn generate(name: &syn::Ident, vis: &syn::Visibility, details: &ArgDetails) -> TokenStream {
+    let schema_fn = syn::Ident::new(&format!("{}_schema", name.unraw()), name.span());
+    let doc = format!(
+        " A JSON Schema describing the arguments of `{}!`, as an object of argument names to values.",
+        name.unraw()
+    );
+    let variants = details
+        .clone()
+        .cfg_variants()
+        .into_iter()
+        .map(|(gate, details)| {
+            let schema = schema(name, &details).to_pretty_string();
+            let schema_fn = quote! {
+                #[doc = #doc]
+                #vis fn #schema_fn() -> &'static str {
+                    #schema
+                }
+            };
+            crate::propagate::gate(&[gate], schema_fn)
+        });
+    quote! { #(#variants)* }
+}
+
+fn schema(name: &syn::Ident, details: &ArgDetails) -> Json {
+    let mut properties = Vec::new();
+    let mut required = Vec::new();
+    for (arg, ty) in details.args.iter().zip(&details.types) {
+        let key = arg.to_string();
+        let mut property = Vec::new();
+        if let Some(description) = details.descriptions.get(&key) {
+            property.push(("description", Json::string(description.trim())));
+        }
+        property.extend(type_schema(ty));
+        match &details.defaults[&key] {
+            Some(default) => {
+                if let Some(default) = literal(default) {
+                    property.push(("default", default));
+                }
+            }
+            // Like a derived `Deserialize`, `#[named(serde)]` takes absent `Option` arguments to be `None`.
+            None if is_optional(ty) => {}
+            None => required.push(Json::string(&key)),
+        }
+        properties.push((key, Json::object(property)));
+    }
+    Json::Object(vec![
+        (
+            "$schema".to_owned(),
+            Json::string("https://json-schema.org/draft/2020-12/schema"),
+        ),
+        ("title".to_owned(), Json::string(&name.unraw().to_string())),
+        ("type".to_owned(), Json::string("object")),
+        ("properties".to_owned(), Json::Object(properties)),
+        ("required".to_owned(), Json::Array(required)),
+        ("additionalProperties".to_owned(), Json::Bool(false)),
+    ])
+}
+
+/// Whether `ty` is an `Option`, perhaps behind a pointer which deserializes as what it points to.
+fn is_optional(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Group(group) => is_optional(&group.elem),
+        syn::Type::Paren(paren) => is_optional(&paren.elem),
+        syn::Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+            Some(last) if last.ident == "Option" => true,
+            Some(last) if last.ident == "Box" || last.ident == "Rc" || last.ident == "Arc" => {
+                match &last.arguments {
+                    syn::PathArguments::AngleBracketed(args) => {
+                        matches!(args.args.first(), Some(syn::GenericArgument::Type(inner)) if is_optional(inner))
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The keywords of a schema which accepts values of `ty`, which are empty if any value is accepted.
+fn type_schema(ty: &syn::Type) -> Vec<(&'static str, Json)> {
+    match ty {
+        syn::Type::Group(group) => type_schema(&group.elem),
+        syn::Type::Paren(paren) => type_schema(&paren.elem),
+        syn::Type::Reference(reference) => type_schema(&reference.elem),
+        syn::Type::Slice(slice) => array(&slice.elem),
+        syn::Type::Array(array_ty) => {
+            let mut schema = array(&array_ty.elem);
+            if let Some(len) = literal(&array_ty.len) {
+                schema.push(("minItems", len.clone()));
+                schema.push(("maxItems", len));
+            }
+            schema
+        }
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => vec![("type", Json::string("null"))],
+        syn::Type::Tuple(tuple) => {
+            let len = Json::Number(tuple.elems.len().to_string());
+            vec![
+                ("type", Json::string("array")),
+                (
+                    "prefixItems",
+                    Json::Array(
+                        tuple
+                            .elems
+                            .iter()
+                            .map(|elem| Json::object(type_schema(elem)))
+                            .collect(),
+                    ),
+                ),
+                ("minItems", len.clone()),
+                ("maxItems", len),
+            ]
+        }
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let last = match path.path.segments.last() {
+                Some(last) => last,
+                None => return Vec::new(),
+            };
+            let args: Vec<_> = match &last.arguments {
+                syn::PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            match (last.ident.to_string().as_str(), args.as_slice()) {
+                ("bool", []) => vec![("type", Json::string("boolean"))],
+                ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", []) => vec![
+                    ("type", Json::string("integer")),
+                    ("minimum", Json::Number("0".to_owned())),
+                ],
+                ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", []) => {
+                    vec![("type", Json::string("integer"))]
+                }
+                ("f32" | "f64", []) => vec![("type", Json::string("number"))],
+                ("char", []) => vec![
+                    ("type", Json::string("string")),
+                    ("minLength", Json::Number("1".to_owned())),
+                    ("maxLength", Json::Number("1".to_owned())),
+                ],
+                ("str" | "String" | "Path" | "PathBuf", []) => {
+                    vec![("type", Json::string("string"))]
+                }
+                ("Box" | "Rc" | "Arc" | "Cow", [inner]) => type_schema(inner),
+                ("Option", [inner]) => {
+                    let mut schema = type_schema(inner);
+                    if schema.is_empty() {
+                        return schema;
+                    }
+                    match schema.iter_mut().find(|(keyword, _)| *keyword == "type") {
+                        Some((_, ty)) if matches!(ty, Json::String(_)) => {
+                            *ty = Json::Array(vec![ty.clone(), Json::string("null")]);
+                            schema
+                        }
+                        _ => vec![(
+                            "anyOf",
+                            Json::Array(vec![
+                                Json::object(schema),
+                                Json::object(vec![("type", Json::string("null"))]),
+                            ]),
+                        )],
+                    }
+                }
+                ("Vec" | "VecDeque", [inner]) => array(inner),
+                ("HashSet" | "BTreeSet", [inner]) => {
+                    let mut schema = array(inner);
+                    schema.push(("uniqueItems", Json::Bool(true)));
+                    schema
+                }
+                ("HashMap" | "BTreeMap", [_key, value]) => vec![
+                    ("type", Json::string("object")),
+                    ("additionalProperties", Json::object(type_schema(value))),
+                ],
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn array(elem: &syn::Type) -> Vec<(&'static str, Json)> {
+    vec![
+        ("type", Json::string("array")),
+        ("items", Json::object(type_schema(elem))),
+    ]
+}
+
+/// The value of an expression which is a literal, or a negated numeric literal.
+fn literal(expr: &dyn ToTokens) -> Option<Json> {
+    let expr = syn::parse2::<syn::Expr>(expr.to_token_stream()).ok()?;
+    let (negated, lit) = match &expr {
+        syn::Expr::Lit(lit) => (false, &lit.lit),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            syn::Expr::Lit(lit) => (true, &lit.lit),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let sign = if negated { "-" } else { "" };
+    match lit {
+        syn::Lit::Int(int) => Some(Json::Number(format!("{}{}", sign, int.base10_digits()))),
+        syn::Lit::Float(float) => {
+            let digits = float.base10_digits();
+            let zero = if digits.ends_with('.') { "0" } else { "" };
+            Some(Json::Number(format!("{}{}{}", sign, digits, zero)))
+        }
+        _ if negated => None,
+        syn::Lit::Str(s) => Some(Json::String(s.value())),
+        syn::Lit::Char(c) => Some(Json::String(c.value().to_string())),
+        syn::Lit::Bool(b) => Some(Json::Bool(b.value)),
+        _ => None,
+    }
+}
+
+/// Just enough of JSON to render a schema.
+#[derive(Clone)]
+enum Json {
+    Bool(bool),
+    /// A number, as it's written.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn string(s: &str) -> Self {
+        Self::String(s.to_owned())
+    }
+
+    fn object(keywords: Vec<(&'static str, Json)>) -> Self {
+        Self::Object(
+            keywords
+                .into_iter()
+                .map(|(keyword, value)| (keyword.to_owned(), value))
+                .collect(),
+        )
+    }
+
+    /// Renders the value with two spaces of indentation per level of nesting.
+    fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = |out: &mut String, depth: usize| {
+            out.push('\n');
+            out.push_str(&"  ".repeat(depth));
+        };
+        match self {
+            Self::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Self::Number(n) => out.push_str(n),
+            Self::String(s) => write_string(out, s),
+            Self::Array(items) if items.is_empty() => out.push_str("[]"),
+            Self::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    indent(out, depth + 1);
+                    item.write(out, depth + 1);
+                }
+                indent(out, depth);
+                out.push(']');
+            }
+            Self::Object(members) if members.is_empty() => out.push_str("{}"),
+            Self::Object(members) => {
+                out.push('{');
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    indent(out, depth + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                }
+                indent(out, depth);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,100 @@
+use crate::arg_reconciler::ArgDetails;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+// Items for `#[named(trace)]`, which records each call of a function made through its macro, noting which arguments
+// took their defaults.
+//
+// The macro only knows which arguments were given while it's expanding, so instead of calling the function directly, it
+// calls a wrapper, passing each argument wrapped in a `named::__private::trace::Arg` which says whether it was given or
+// defaulted. The wrapper records the call, using whichever of the `tracing` and `log` features of `named` are enabled,
+// and then calls the function.
+
+/// A value given for an argument, as it's passed to the function.
+pub fn given(traced: bool, value: TokenStream) -> TokenStream {
+    if traced {
+        quote! { ::named::__private::trace::Arg::given(#value) }
+    } else {
+        value
+    }
+}
+
+/// An argument's default, as it's passed to the function.
+pub fn defaulted(traced: bool, value: TokenStream) -> TokenStream {
+    if traced {
+        quote! { ::named::__private::trace::Arg::defaulted(#value) }
+    } else {
+        value
+    }
+}
+
+/// The name of the wrapper which records calls of the function before making them.
+pub fn wrapper_name(dunder_name: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&format!("{}_traced", dunder_name), dunder_name.span())
+}
+
+/// Generates the wrapper, which takes the same generic parameters as the function, and each of its arguments wrapped in
+/// an `Arg`.
+pub fn generate(
+    name: &syn::Ident,
+    wrapper: &syn::Ident,
+    target: &syn::Ident,
+    f: &syn::ItemFn,
+    details: &ArgDetails,
+) -> TokenStream {
+    let name = name.unraw().to_string();
+    let vis = &f.vis;
+    let syn::Signature {
+        asyncness,
+        unsafety,
+        generics,
+        output,
+        ..
+    } = &f.sig;
+    let (_impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+    let turbofish_params: Vec<_> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(ty) => Some(&ty.ident),
+            syn::GenericParam::Const(c) => Some(&c.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+    let turbofish = if turbofish_params.is_empty() {
+        None
+    } else {
+        Some(quote! { ::<#(#turbofish_params),*> })
+    };
+    let kind = if asyncness.is_some() {
+        quote! { @async }
+    } else {
+        quote! { @sync }
+    };
+
+    let wrappers = details
+        .clone()
+        .cfg_variants()
+        .into_iter()
+        .map(|(gate, details)| {
+            let args = &details.args;
+            let types = &details.types;
+            let call = quote! { #target #turbofish(#(#args.value),*) };
+            let call = match unsafety {
+                Some(_) => quote! { unsafe { #call } },
+                None => call,
+            };
+            let wrapper = quote! {
+                #[doc(hidden)]
+                #[allow(clippy::too_many_arguments)]
+                #vis #asyncness #unsafety fn #wrapper #generics(
+                    #(#args: ::named::__private::trace::Arg<#types>),*
+                ) #output #where_clause {
+                    ::named::__trace_span!(#kind #name [#(#args),*] ::named::__trace_log!(#name [#(#args),*] #call))
+                }
+            };
+            crate::propagate::gate(&[gate], wrapper)
+        });
+    quote! { #(#wrappers)* }
+}
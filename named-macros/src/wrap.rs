@@ -0,0 +1,40 @@
+use crate::attr_parser::Attributes;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+/// `#[doc = "..."] fn name = path::to::function(arg: Type, ...) -> Return; defaults(...)`
+pub struct Wrap {
+    pub attrs: Vec<syn::Attribute>,
+    _fn_token: syn::Token![fn],
+    pub name: syn::Ident,
+    _eq_token: syn::Token![=],
+    pub path: syn::ExprPath,
+    _paren_token: syn::token::Paren,
+    pub inputs: Punctuated<syn::FnArg, syn::Token![,]>,
+    _output: syn::ReturnType,
+    pub attr: Attributes,
+}
+
+impl Parse for Wrap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            attrs: input.call(syn::Attribute::parse_outer)?,
+            _fn_token: input.parse()?,
+            name: input.parse()?,
+            _eq_token: input.parse()?,
+            path: input.parse()?,
+            _paren_token: syn::parenthesized!(content in input),
+            inputs: content.parse_terminated(syn::FnArg::parse)?,
+            _output: input.parse()?,
+            attr: {
+                if input.is_empty() {
+                    Attributes::empty()
+                } else {
+                    input.parse::<syn::Token![;]>()?;
+                    input.parse()?
+                }
+            },
+        })
+    }
+}
@@ -0,0 +1,61 @@
+// Support for `#[named(cli)]`, which shows each argument's default in its help text.
+//
+// This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+
+use std::fmt;
+
+// Defaults are shown with their `Display` implementations if they have them, as clap shows its own defaults, or else
+// with their `Debug` implementations, and not at all if they have neither. Which applies is picked by method
+// resolution: `(&&DefaultValue(&value)).render()` prefers `ViaDisplay`, which is implemented for the receiver's own
+// type, then `ViaDebug`, which needs auto-referencing, and then `ViaNeither`, which needs dereferencing.
+
+pub struct DefaultValue<'a, T>(pub &'a T);
+
+pub trait ViaDisplay {
+    fn render(&self) -> Option<String>;
+}
+
+impl<T: fmt::Display> ViaDisplay for &DefaultValue<'_, T> {
+    fn render(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+pub trait ViaDebug {
+    fn render(&self) -> Option<String>;
+}
+
+impl<T: fmt::Debug> ViaDebug for &&DefaultValue<'_, T> {
+    fn render(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+pub trait ViaNeither {
+    fn render(&self) -> Option<String>;
+}
+
+impl<T> ViaNeither for DefaultValue<'_, T> {
+    fn render(&self) -> Option<String> {
+        None
+    }
+}
+
+/// An argument's help text, followed by its default, if it has one which can be shown.
+pub fn help(description: &str, default: Option<String>) -> String {
+    match default {
+        Some(default) if description.is_empty() => format!("[default: {}]", default),
+        Some(default) => format!("{} [default: {}]", description, default),
+        None => description.to_owned(),
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cli_default {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::cli::{ViaDebug as _, ViaDisplay as _, ViaNeither as _};
+        (&&$crate::__private::cli::DefaultValue(&$value)).render()
+    }};
+}
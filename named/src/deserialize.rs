@@ -0,0 +1,232 @@
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Why calling a function with arguments deserialized by `#[named(serde)]` failed.
+///
+/// Each variant wraps the deserializer's own error, and every variant other than `Malformed` names the argument at
+/// fault.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeserializeError<E> {
+    /// The arguments weren't a map of argument names to values, or couldn't be read at all.
+    Malformed(E),
+    /// An argument isn't one of the function's parameters.
+    UnknownArgument {
+        argument: String,
+        expected: &'static [&'static str],
+        error: E,
+    },
+    /// An argument was given more than once.
+    DuplicateArgument { argument: &'static str, error: E },
+    /// A parameter without a default wasn't given a value.
+    MissingArgument { argument: &'static str, error: E },
+    /// An argument's value couldn't be deserialized as its parameter's type.
+    InvalidArgument { argument: &'static str, error: E },
+}
+
+impl<E> DeserializeError<E> {
+    /// The argument at fault, if the error is about one.
+    pub fn argument(&self) -> Option<&str> {
+        match self {
+            Self::Malformed(_) => None,
+            Self::UnknownArgument { argument, .. } => Some(argument),
+            Self::DuplicateArgument { argument, .. }
+            | Self::MissingArgument { argument, .. }
+            | Self::InvalidArgument { argument, .. } => Some(argument),
+        }
+    }
+
+    /// The deserializer's own error.
+    pub fn error(&self) -> &E {
+        match self {
+            Self::Malformed(error)
+            | Self::UnknownArgument { error, .. }
+            | Self::DuplicateArgument { error, .. }
+            | Self::MissingArgument { error, .. }
+            | Self::InvalidArgument { error, .. } => error,
+        }
+    }
+
+    /// Takes the deserializer's own error.
+    pub fn into_error(self) -> E {
+        match self {
+            Self::Malformed(error)
+            | Self::UnknownArgument { error, .. }
+            | Self::DuplicateArgument { error, .. }
+            | Self::MissingArgument { error, .. }
+            | Self::InvalidArgument { error, .. } => error,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for DeserializeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(error) => write!(f, "Couldn't deserialize arguments: {}", error),
+            Self::UnknownArgument {
+                argument, expected, ..
+            } => write!(
+                f,
+                "Unrecognized named argument - got value for argument `{}` but only expected {}",
+                argument,
+                crate::format_names(expected),
+            ),
+            Self::DuplicateArgument { argument, .. } => {
+                write!(f, "Argument `{}` was given more than once", argument)
+            }
+            Self::MissingArgument { argument, .. } => write!(
+                f,
+                "Must specify value for non-defaulted argument: `{}`",
+                argument
+            ),
+            Self::InvalidArgument { argument, error } => {
+                write!(f, "Invalid value for argument `{}`: {}", argument, error)
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for DeserializeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error())
+    }
+}
+
+// The items below are used by the code `#[named(serde)]` generates.
+// They're considered a private implementation detail, and should not be relied on - they may change or be removed in a patch release.
+
+/// What went wrong while deserializing arguments, which is recorded as it happens, as the deserializer's own error
+/// type can't carry it, and then attached to that error once deserializing gives up.
+pub enum Failure {
+    Unknown(String, &'static [&'static str]),
+    Duplicate(&'static str),
+    Missing(&'static str),
+    Invalid(&'static str),
+}
+
+/// The name of an argument.
+pub struct Key(String);
+
+impl Key {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = Key;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an argument name")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Key, E> {
+                Ok(Key(value.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Key, E> {
+                Ok(Key(value))
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Key, E> {
+                Ok(Key(String::from_utf8_lossy(value).into_owned()))
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyVisitor)
+    }
+}
+
+/// Deserializes the value of `argument` into `slot`, unless it's already been given.
+pub fn value<'de, A: MapAccess<'de>, T: Deserialize<'de>>(
+    map: &mut A,
+    slot: &mut Option<T>,
+    argument: &'static str,
+    failure: &mut Option<Failure>,
+) -> Result<(), A::Error> {
+    if slot.is_some() {
+        *failure = Some(Failure::Duplicate(argument));
+        return Err(de::Error::duplicate_field(argument));
+    }
+    match map.next_value() {
+        Ok(value) => {
+            *slot = Some(value);
+            Ok(())
+        }
+        Err(error) => {
+            *failure = Some(Failure::Invalid(argument));
+            Err(error)
+        }
+    }
+}
+
+/// The value of `argument`, which has no default, so must have been given, unless its type can be deserialized from
+/// nothing at all, as an `Option` can, as `None`, in the same way as a derived `Deserialize` implementation allows.
+pub fn required<'de, T: Deserialize<'de>, E: de::Error>(
+    slot: Option<T>,
+    argument: &'static str,
+    failure: &mut Option<Failure>,
+) -> Result<T, E> {
+    match slot {
+        Some(value) => Ok(value),
+        None => match T::deserialize(Absent(argument, PhantomData)) {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                *failure = Some(Failure::Missing(argument));
+                Err(error)
+            }
+        },
+    }
+}
+
+/// The value of an argument which wasn't given, which is `None` if it's optional, and missing otherwise.
+struct Absent<E>(&'static str, PhantomData<E>);
+
+impl<'de, E: de::Error> Deserializer<'de> for Absent<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, E> {
+        Err(E::missing_field(self.0))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_none()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// The error for `key`, which isn't one of the `expected` arguments.
+pub fn unknown<E: de::Error>(
+    key: Key,
+    expected: &'static [&'static str],
+    failure: &mut Option<Failure>,
+) -> E {
+    let error = E::unknown_field(key.as_str(), expected);
+    *failure = Some(Failure::Unknown(key.0, expected));
+    error
+}
+
+/// Attaches what went wrong, if anything was recorded, to the deserializer's error.
+pub fn error<E>(failure: Option<Failure>, error: E) -> DeserializeError<E> {
+    match failure {
+        None => DeserializeError::Malformed(error),
+        Some(Failure::Unknown(argument, expected)) => DeserializeError::UnknownArgument {
+            argument,
+            expected,
+            error,
+        },
+        Some(Failure::Duplicate(argument)) => {
+            DeserializeError::DuplicateArgument { argument, error }
+        }
+        Some(Failure::Missing(argument)) => DeserializeError::MissingArgument { argument, error },
+        Some(Failure::Invalid(argument)) => DeserializeError::InvalidArgument { argument, error },
+    }
+}
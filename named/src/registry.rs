@@ -0,0 +1,443 @@
+use crate::Signature;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Every function registered with `#[named(register)]`, which can be looked up and called by name at runtime.
+///
+/// Functions can be named by their path, e.g. `my_crate::shapes::area`, or by their name alone, e.g. `area`, as long as
+/// only one registered function has that name.
+///
+/// Calls are checked in the same way as calls of a function's macro are at compile time: every argument must be one of
+/// the function's parameters, every parameter without a default must be given a value, and parameters which aren't
+/// given a value take their defaults.
+pub struct Registry {
+    by_path: HashMap<String, &'static Function>,
+    by_name: HashMap<&'static str, Vec<&'static Function>>,
+}
+
+impl Registry {
+    /// Collects every registered function, from every crate linked into the program.
+    pub fn new() -> Self {
+        let mut by_path = HashMap::new();
+        let mut by_name: HashMap<_, Vec<_>> = HashMap::new();
+        for function in inventory::iter::<Function> {
+            by_path.insert(function.path(), function);
+            by_name
+                .entry(function.signature.name)
+                .or_default()
+                .push(function);
+        }
+        Self { by_path, by_name }
+    }
+
+    /// Looks up a registered function by its path, or by its name if only one registered function has that name.
+    pub fn get(&self, name: &str) -> Option<&'static Function> {
+        self.lookup(name).ok()
+    }
+
+    /// Every registered function, in no particular order.
+    pub fn functions(&self) -> impl Iterator<Item = &'static Function> + '_ {
+        self.by_path.values().copied()
+    }
+
+    /// Calls the function registered as `name` (which may be its path) with `args`, converting each argument to its
+    /// parameter's type, and the function's result back, with `V`'s implementation of [`Value`].
+    pub fn call<V: Value>(&self, name: &str, args: Args<V>) -> Result<V, CallError> {
+        self.lookup(name)?.call(args)
+    }
+
+    fn lookup(&self, name: &str) -> Result<&'static Function, CallError> {
+        if let Some(function) = self.by_path.get(name) {
+            return Ok(function);
+        }
+        match self.by_name.get(name).map(Vec::as_slice) {
+            Some([function]) => Ok(function),
+            Some(functions) => {
+                let mut candidates: Vec<_> =
+                    functions.iter().map(|function| function.path()).collect();
+                candidates.sort_unstable();
+                Err(CallError::AmbiguousFunction {
+                    name: name.to_owned(),
+                    candidates,
+                })
+            }
+            None => Err(CallError::UnknownFunction(name.to_owned())),
+        }
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.by_path.keys()).finish()
+    }
+}
+
+/// A function registered with `#[named(register)]`.
+pub struct Function {
+    /// The function's name and parameters.
+    pub signature: Signature,
+    /// The path of the module the function is defined in, as given by `module_path!()`.
+    pub module: &'static str,
+    // The fields below are filled in by `#[named(register)]`.
+    // They're considered a private implementation detail, and should not be relied on - they may change or be removed in a patch release.
+    #[doc(hidden)]
+    pub types: &'static [Type],
+    #[doc(hidden)]
+    pub output: Type,
+    #[doc(hidden)]
+    pub defaults: &'static [Option<fn() -> AnyBox>],
+    #[doc(hidden)]
+    pub call: fn(Vec<AnyBox>) -> Result<AnyBox, CallError>,
+}
+
+type AnyBox = Box<dyn Any>;
+
+inventory::collect!(Function);
+
+impl Function {
+    /// The function's path, e.g. `my_crate::shapes::area`, which it can always be looked up by.
+    pub fn path(&self) -> String {
+        format!("{}::{}", self.module, self.signature.name)
+    }
+
+    /// Calls the function with `args`, as [`Registry::call`] does.
+    pub fn call<V: Value>(&self, args: Args<V>) -> Result<V, CallError> {
+        let function = self.signature.name;
+        let params = self.signature.params;
+        let mut values: Vec<Option<AnyBox>> = params.iter().map(|_| None).collect();
+        for (name, value) in args.values {
+            let index = params
+                .iter()
+                .position(|param| param.name == name)
+                .ok_or_else(|| CallError::UnknownArgument {
+                    function,
+                    argument: name.to_owned(),
+                    expected: params.iter().map(|param| param.name).collect(),
+                })?;
+            if values[index].is_some() {
+                return Err(CallError::DuplicateArgument {
+                    function,
+                    argument: params[index].name,
+                });
+            }
+            let argument = params[index].name;
+            let value = value.map_err(|given| CallError::UnsupportedArgument {
+                function,
+                argument,
+                ty: given.name(),
+            })?;
+            let ty = self.types[index];
+            let wrong_type = || CallError::WrongType {
+                function,
+                argument,
+                expected: ty.name(),
+            };
+            let value = value.into_arg(ty).map_err(|_value| wrong_type())?;
+            // `into_arg` is trusted to produce the type it was asked for, but not relied on to.
+            if (*value).type_id() != ty.id() {
+                return Err(wrong_type());
+            }
+            values[index] = Some(value);
+        }
+
+        let mut missing = Vec::new();
+        let values: Vec<_> = values
+            .into_iter()
+            .zip(params.iter().zip(self.defaults))
+            .filter_map(|(value, (param, default))| {
+                let value = value.or_else(|| default.map(|default| default()));
+                if value.is_none() {
+                    missing.push(param.name);
+                }
+                value
+            })
+            .collect();
+        if !missing.is_empty() {
+            return Err(CallError::MissingArguments {
+                function,
+                arguments: missing,
+            });
+        }
+
+        V::from_any((self.call)(values)?, self.output).map_err(|_result| {
+            CallError::UnsupportedResult {
+                function,
+                ty: self.output.name(),
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Function")
+            .field("signature", &self.signature)
+            .field("module", &self.module)
+            .finish()
+    }
+}
+
+/// The type of a parameter or result of a registered function, which a [`Value`] converts to or from.
+#[derive(Clone, Copy)]
+pub struct Type {
+    name: &'static str,
+    id: fn() -> TypeId,
+}
+
+impl Type {
+    /// The type `T`.
+    pub fn of<T: Any>() -> Self {
+        Self {
+            name: std::any::type_name::<T>(),
+            id: TypeId::of::<T>,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn new<T: Any>(name: &'static str) -> Self {
+        Self {
+            name,
+            id: TypeId::of::<T>,
+        }
+    }
+
+    /// The type's name, as it's written in the function's signature if it's from one.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The type's `TypeId`.
+    pub fn id(&self) -> TypeId {
+        (self.id)()
+    }
+
+    /// Whether this is the type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.id() == TypeId::of::<T>()
+    }
+}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl fmt::Debug for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// A dynamically typed value, which registered functions are called with and return.
+///
+/// Implementing this for your own value type, e.g. that of a scripting language, is how its values are converted to
+/// the types of functions' parameters, and how functions' results are converted back. Conversions are between values
+/// and boxed `Any`s of the [`Type`] asked for, e.g. checking `ty.is::<u32>()` before producing a `Box<u32>`.
+///
+/// It's implemented for `Box<dyn Any>`, which converts to and from only exactly the type it holds.
+pub trait Value: Sized {
+    /// Converts the value to the type `ty`, or gives it back if it can't be.
+    fn into_arg(self, ty: Type) -> Result<Box<dyn Any>, Self>;
+
+    /// Converts a boxed value of the type `ty` to a value, or gives it back if it can't be.
+    fn from_any(value: Box<dyn Any>, ty: Type) -> Result<Self, Box<dyn Any>>;
+}
+
+impl Value for Box<dyn Any> {
+    fn into_arg(self, ty: Type) -> Result<Box<dyn Any>, Self> {
+        if (*self).type_id() == ty.id() {
+            Ok(self)
+        } else {
+            Err(self)
+        }
+    }
+
+    fn from_any(value: Box<dyn Any>, _ty: Type) -> Result<Self, Box<dyn Any>> {
+        Ok(value)
+    }
+}
+
+/// Arguments to call a registered function with, as built by [`args!`](macro.args.html).
+pub struct Args<V> {
+    values: Vec<(&'static str, Result<V, Type>)>,
+}
+
+impl<V: Value> Args<V> {
+    /// No arguments, which [`with`](Self::with) and [`with_value`](Self::with_value) add to.
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    /// Adds the argument `name`, converting its value with `V`'s implementation of [`Value`].
+    pub fn with<T: Any>(mut self, name: &'static str, value: T) -> Self {
+        let ty = Type::of::<T>();
+        let value = V::from_any(Box::new(value), ty).map_err(|_value| ty);
+        self.values.push((name, value));
+        self
+    }
+
+    /// Adds the argument `name`, whose value is already a `V`.
+    pub fn with_value(mut self, name: &'static str, value: V) -> Self {
+        self.values.push((name, Ok(value)));
+        self
+    }
+}
+
+impl<V: Value> Default for Args<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Takes the next of the arguments a registered function is called with, which `Function::call` has already checked
+/// is of its parameter's type.
+#[doc(hidden)]
+pub fn take_arg<T: Any>(
+    args: &mut impl Iterator<Item = AnyBox>,
+    function: &'static str,
+    argument: &'static str,
+) -> Result<T, CallError> {
+    let wrong_type = || CallError::WrongType {
+        function,
+        argument,
+        expected: std::any::type_name::<T>(),
+    };
+    let value = args.next().ok_or_else(wrong_type)?;
+    value
+        .downcast::<T>()
+        .map(|value| *value)
+        .map_err(|_value| wrong_type())
+}
+
+/// Builds the [`Args`] to call a registered function with, e.g. `args! { width: 3, height: 4 }`.
+///
+/// Each value is converted with [`Value::from_any`], so must be of a type the value type accepts.
+#[macro_export]
+macro_rules! args {
+    ($($name:ident : $value:expr),* $(,)?) => {
+        $crate::Args::new()$(.with(::core::stringify!($name), $value))*
+    };
+}
+
+/// Why calling a registered function failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CallError {
+    /// No function is registered with the name.
+    UnknownFunction(String),
+    /// More than one function is registered with the name, so it must be called by its path, which is one of
+    /// `candidates`.
+    AmbiguousFunction {
+        name: String,
+        candidates: Vec<String>,
+    },
+    /// An argument isn't one of the function's parameters.
+    UnknownArgument {
+        function: &'static str,
+        argument: String,
+        expected: Vec<&'static str>,
+    },
+    /// An argument was given more than once.
+    DuplicateArgument {
+        function: &'static str,
+        argument: &'static str,
+    },
+    /// Parameters without defaults weren't given values.
+    MissingArguments {
+        function: &'static str,
+        arguments: Vec<&'static str>,
+    },
+    /// An argument's value, of type `ty`, couldn't be converted to a value.
+    UnsupportedArgument {
+        function: &'static str,
+        argument: &'static str,
+        ty: &'static str,
+    },
+    /// An argument's value couldn't be converted to its parameter's type.
+    WrongType {
+        function: &'static str,
+        argument: &'static str,
+        expected: &'static str,
+    },
+    /// The function's result couldn't be converted to a value.
+    UnsupportedResult {
+        function: &'static str,
+        ty: &'static str,
+    },
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFunction(name) => write!(f, "No function is registered as `{}`", name),
+            Self::AmbiguousFunction { name, candidates } => write!(
+                f,
+                "More than one function is registered as `{}` - call one of them by its path: {}",
+                name,
+                candidates.join(", "),
+            ),
+            Self::UnknownArgument {
+                function,
+                argument,
+                expected,
+            } => write!(
+                f,
+                "Unrecognized named argument to `{}` - got value for argument `{}` but only expected {}",
+                function,
+                argument,
+                crate::format_names(expected),
+            ),
+            Self::DuplicateArgument { function, argument } => write!(
+                f,
+                "Argument `{}` to `{}` was given more than once",
+                argument, function
+            ),
+            Self::MissingArguments {
+                function,
+                arguments,
+            } => {
+                let maybe_s = if arguments.len() == 1 { "" } else { "s" };
+                write!(
+                    f,
+                    "Must specify value{} for non-defaulted argument{} to `{}`: {}",
+                    maybe_s,
+                    maybe_s,
+                    function,
+                    crate::format_names(arguments),
+                )
+            }
+            Self::UnsupportedArgument {
+                function,
+                argument,
+                ty,
+            } => write!(
+                f,
+                "Argument `{}` to `{}`, of type `{}`, couldn't be converted to a value",
+                argument, function, ty
+            ),
+            Self::WrongType {
+                function,
+                argument,
+                expected,
+            } => write!(
+                f,
+                "Argument `{}` to `{}` couldn't be converted to `{}`",
+                argument, function, expected
+            ),
+            Self::UnsupportedResult { function, ty } => write!(
+                f,
+                "The result of `{}`, of type `{}`, couldn't be converted to a value",
+                function, ty
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CallError {}
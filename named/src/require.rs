@@ -0,0 +1,62 @@
+// Items generated for options which need an optional feature of this crate are wrapped in these macros, so that using
+// an option without its feature reports which feature to enable, rather than failing to find what the items use.
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_serde {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_serde {
+    ($($item:item)*) => {
+        ::core::compile_error!(
+            "`#[named(serde)]` requires the `serde` feature of `named` - enable it in your Cargo.toml."
+        );
+    };
+}
+
+#[cfg(feature = "cli")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_cli {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "cli"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_cli {
+    ($($item:item)*) => {
+        ::core::compile_error!(
+            "`#[named(cli)]` requires the `cli` feature of `named` - enable it in your Cargo.toml."
+        );
+    };
+}
+
+#[cfg(feature = "register")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_register {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "register"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_register {
+    ($($item:item)*) => {
+        ::core::compile_error!(
+            "`#[named(register)]` requires the `register` feature of `named` - enable it in your Cargo.toml."
+        );
+    };
+}
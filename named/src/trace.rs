@@ -0,0 +1,180 @@
+// Support for `#[named(trace)]`, which records each call of a function, along with which of its arguments were given
+// and which took their defaults.
+//
+// Calls made through the function's macro pass each argument to a wrapper, wrapped in an `Arg` which says whether it was
+// defaulted. The wrapper records the call with `__trace_span!` and `__trace_log!`, which are no-ops unless the `tracing`
+// and `log` features (respectively) are enabled, and then calls the function.
+//
+// This is considered a private implementation detail, and should not be relied on - it may change or be removed in a patch release.
+
+use std::fmt;
+
+/// An argument to a traced function, and whether it took its default.
+pub struct Arg<T> {
+    pub value: T,
+    pub defaulted: bool,
+}
+
+impl<T> Arg<T> {
+    pub fn given(value: T) -> Self {
+        Self {
+            value,
+            defaulted: false,
+        }
+    }
+
+    pub fn defaulted(value: T) -> Self {
+        Self {
+            value,
+            defaulted: true,
+        }
+    }
+}
+
+// Arguments are recorded with their `Debug` implementations, if they have them, and as `_` if not. Which applies is
+// picked by method resolution: `(&DebugValue(&value)).as_debug()` prefers `ViaDebug`, which needs no auto-referencing,
+// but only applies if the value implements `Debug`.
+
+pub struct DebugValue<'a, T>(pub &'a T);
+
+pub trait ViaDebug<'a> {
+    fn as_debug(&self) -> &'a dyn fmt::Debug;
+}
+
+impl<'a, T: fmt::Debug> ViaDebug<'a> for DebugValue<'a, T> {
+    fn as_debug(&self) -> &'a dyn fmt::Debug {
+        self.0
+    }
+}
+
+pub trait ViaOpaque<'a> {
+    fn as_debug(&self) -> &'a dyn fmt::Debug;
+}
+
+impl<'a, T> ViaOpaque<'a> for &DebugValue<'a, T> {
+    fn as_debug(&self) -> &'a dyn fmt::Debug {
+        &Opaque
+    }
+}
+
+struct Opaque;
+
+impl fmt::Debug for Opaque {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("_")
+    }
+}
+
+/// A call, as it's logged, e.g. `greet(name = "World", greeting = "Hello" (defaulted))`.
+pub struct Call<'a> {
+    pub name: &'static str,
+    pub args: &'a [Recorded<'a>],
+}
+
+pub struct Recorded<'a> {
+    pub name: &'static str,
+    pub value: &'a dyn fmt::Debug,
+    pub defaulted: bool,
+}
+
+impl fmt::Display for Call<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (index, arg) in self.args.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} = {:?}", arg.name, arg.value)?;
+            if arg.defaulted {
+                f.write_str(" (defaulted)")?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_debug {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::trace::{ViaDebug as _, ViaOpaque as _};
+        (&$crate::__private::trace::DebugValue(&$value)).as_debug()
+    }};
+}
+
+/// Runs a call of a traced function in a span named after it, with a field for each argument, and a `.defaulted` field
+/// which is set for each argument which took its default.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_span {
+    (@sync $name:literal [$($arg:ident),*] $call:expr) => {{
+        let span = $crate::__trace_span!(@span $name [$($arg),*]);
+        let _entered = span.enter();
+        $call
+    }};
+    (@async $name:literal [$($arg:ident),*] $call:expr) => {{
+        let span = $crate::__trace_span!(@span $name [$($arg),*]);
+        $crate::__private::tracing::Instrument::instrument($call, span).await
+    }};
+    (@span $name:literal [$($arg:ident),*]) => {{
+        let span = $crate::__private::tracing::trace_span!(
+            $name,
+            $(
+                $arg = $crate::__private::tracing::field::debug($crate::__trace_debug!($arg.value)),
+                $arg.defaulted = $crate::__private::tracing::field::Empty,
+            )*
+        );
+        $(
+            if $arg.defaulted {
+                span.record(::core::concat!(::core::stringify!($arg), ".defaulted"), true);
+            }
+        )*
+        span
+    }};
+}
+
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_span {
+    (@sync $name:literal [$($arg:ident),*] $call:expr) => {
+        $call
+    };
+    (@async $name:literal [$($arg:ident),*] $call:expr) => {
+        $call.await
+    };
+}
+
+/// Logs a call of a traced function, at the trace level, before making it.
+#[cfg(feature = "log")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_log {
+    ($name:literal [$($arg:ident),*] $call:expr) => {{
+        $crate::__private::log::trace!(
+            "{}",
+            $crate::__private::trace::Call {
+                name: $name,
+                args: &[$(
+                    $crate::__private::trace::Recorded {
+                        name: ::core::stringify!($arg),
+                        value: $crate::__trace_debug!($arg.value),
+                        defaulted: $arg.defaulted,
+                    }
+                ),*],
+            }
+        );
+        $call
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_log {
+    ($name:literal [$($arg:ident),*] $call:expr) => {
+        $call
+    };
+}
@@ -0,0 +1,10 @@
+use named::named;
+
+#[named(register, defaults(times = 1))]
+fn greet(name: String, times: usize) -> String {
+    name.repeat(times)
+}
+
+fn main() {
+    greet!(name = "World".to_owned());
+}
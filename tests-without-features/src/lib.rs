@@ -0,0 +1,7 @@
+#[test]
+fn trybuild() {
+    let directory = std::path::Path::new(".");
+
+    let testcases = trybuild::TestCases::new();
+    testcases.compile_fail(directory.join("compile_fail/*.rs"));
+}
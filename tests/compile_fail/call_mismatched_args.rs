@@ -0,0 +1,11 @@
+use named::named;
+
+#[named(defaults(c = 3))]
+fn sum(a: u8, b: u8, c: u8) -> u8 {
+    a + b + c
+}
+
+fn main() {
+    let _ = sum!(a = 1, b = 2);
+    let _ = named::call!(sum(c = 1, d = 2, c = 3));
+}
@@ -0,0 +1,21 @@
+use named::named_trait;
+
+#[named_trait]
+trait Counter {
+    #[named(defaults(by = 1))]
+    fn add(&mut self, by: u32) -> u32;
+}
+
+struct Total(u32);
+
+impl Counter for Total {
+    fn add(&mut self, by: u32) -> u32 {
+        self.0 += by;
+        self.0
+    }
+}
+
+fn main() {
+    let mut total = Total(0);
+    let _ = named::call!(total.add(by = 2));
+}
@@ -0,0 +1,11 @@
+use named::named;
+
+#[named(defaults(port = 80))]
+fn connect(host: &str, port: u16, #[cfg(feature = "tls")] cert: &str) -> String {
+    format!("{}:{}", host, port)
+}
+
+fn main() {
+    connect!(host = "localhost", cert = "cert.pem");
+    connect!(host = "localhost", cert = "cert.pem", port = 443);
+}
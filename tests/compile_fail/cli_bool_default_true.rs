@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(cli, defaults(verbose = true))]
+fn run(verbose: bool) -> bool {
+    verbose
+}
+
+fn main() {}
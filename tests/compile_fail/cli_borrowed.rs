@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(cli)]
+fn greet(name: &str, suffix: Option<&str>) -> String {
+    format!("Hello, {}{}", name, suffix.unwrap_or(""))
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(cli)]
+fn parse<T: std::str::FromStr>(input: String) -> Option<T> {
+    input.parse().ok()
+}
+
+fn main() {}
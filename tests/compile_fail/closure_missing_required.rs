@@ -0,0 +1,4 @@
+fn main() {
+    named::closure!(add = |a: u8, b: u8 = 2| a + b);
+    add!(b = 1);
+}
@@ -0,0 +1,14 @@
+use named::named;
+
+fn limit() -> usize {
+    8
+}
+
+#[named(const_defaults, defaults(limit = limit()))]
+fn describe(limit: usize) -> String {
+    format!("{}", limit)
+}
+
+fn main() {
+    describe!();
+}
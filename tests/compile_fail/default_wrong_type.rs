@@ -0,0 +1,11 @@
+use named::named;
+
+#[named(defaults(a = "x"))]
+fn id(a: u8) -> u8 {
+    a
+}
+
+fn main() {
+    // The default is never used, but is still checked.
+    id!(a = 1);
+}
@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use named::named;
+
+#[named(defaults(b = 1))]
+#[deprecated(note = "Use `add!` instead.")]
+fn old_add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn main() {
+    let _ = old_add!(a = 1);
+}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(docs(b = "Not an argument."))]
+fn identity(a: u8) -> u8 {
+    a
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(ffi)]
+fn first<T: Copy>(values: *const T) -> T {
+    unsafe { *values }
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(ffi)]
+pub fn greet(name: String) {
+    println!("Hello, {}!", name);
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(ffi)]
+pub fn greet(name: &str) {
+    println!("Hello, {}!", name);
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(ffi)]
+pub fn count<'a>(value: u32) -> u32 {
+    value
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(types(U = String))]
+fn make<T: Default>() -> T {
+    T::default()
+}
+
+fn main() {}
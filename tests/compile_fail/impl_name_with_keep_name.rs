@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(keep_name, impl_name = or_impl)]
+fn or(a: bool, b: bool) -> bool {
+    a || b
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![deny(unused_must_use)]
+
+use named::named;
+
+#[named(defaults(b = 1))]
+#[must_use]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn main() {
+    add!(a = 1);
+}
@@ -0,0 +1,14 @@
+use named::named_module;
+
+#[named_module(all_pub)]
+mod text {
+    fn private(s: &str) -> String {
+        s.to_owned()
+    }
+
+    pub fn echo(s: &str) -> String {
+        private!(s = s)
+    }
+}
+
+fn main() {}
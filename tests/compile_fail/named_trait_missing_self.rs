@@ -0,0 +1,11 @@
+use named::named_trait;
+
+#[named_trait]
+pub trait Renderer {
+    #[defaults(width = 1)]
+    fn draw(&self, width: u8) -> String;
+}
+
+fn main() {
+    let _ = Renderer_draw!(width = 2);
+}
@@ -0,0 +1,9 @@
+use named::named_trait;
+
+#[named_trait]
+pub trait Renderer {
+    #[defaults(height = 1)]
+    fn draw(&self, width: u8) -> String;
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use named::named;
+
+#[derive(Default)]
+pub struct FetchOptions {
+    pub retries: u8,
+}
+
+#[named(options = FetchOptions)]
+fn fetch(path: &str, options: FetchOptions) -> u8 {
+    let _ = path;
+    options.retries
+}
+
+fn main() {
+    let _ = fetch!(path = "/", retries = 3, options = FetchOptions::default());
+}
@@ -0,0 +1,14 @@
+use named::named;
+
+#[derive(Default)]
+pub struct FetchOptions {
+    pub retries: u8,
+}
+
+#[named(options = FetchOpts)]
+fn fetch(path: &str, options: FetchOptions) -> u8 {
+    let _ = path;
+    options.retries
+}
+
+fn main() {}
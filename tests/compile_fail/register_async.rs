@@ -0,0 +1,8 @@
+use named::named;
+
+#[named(register)]
+async fn fetch(url: String) -> String {
+    url
+}
+
+fn main() {}
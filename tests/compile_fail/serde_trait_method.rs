@@ -0,0 +1,9 @@
+use named::named_trait;
+
+#[named_trait]
+trait Counter {
+    #[named(serde, defaults(by = 1))]
+    fn bump(&mut self, by: u32);
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use named::named;
+
+pub struct Base {
+    pub a: bool,
+    pub b: bool,
+}
+
+#[named(defaults(a = false, b = false))]
+fn or(a: bool, b: bool) -> bool {
+    a || b
+}
+
+fn main() {
+    let base = Base { a: true, b: true };
+    let _ = or!(..base, c = true);
+}
@@ -0,0 +1,11 @@
+use named::named;
+
+#[named(defaults(offset = 0))]
+unsafe fn read(p: *const u8, offset: usize) -> u8 {
+    *p.add(offset)
+}
+
+fn main() {
+    let bytes = [1, 2, 3];
+    read!(p = bytes.as_ptr());
+}
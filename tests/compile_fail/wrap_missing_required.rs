@@ -0,0 +1,5 @@
+named::wrap!(fn repeat = str::repeat(s: &str, n: usize) -> String);
+
+fn main() {
+    let _ = repeat!(s = "ab");
+}
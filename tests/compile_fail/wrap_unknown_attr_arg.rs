@@ -0,0 +1,3 @@
+named::wrap!(fn repeat = str::repeat(s: &str, n: usize) -> String; defaults(count = 2));
+
+fn main() {}
@@ -0,0 +1,115 @@
+use named::{named, named_trait};
+use std::str::FromStr;
+
+#[named(defaults(b = 2, c = 3))]
+fn sum(a: u8, b: u8, c: u8) -> u8 {
+    a + b + c
+}
+
+#[named(defaults(value = T::default()))]
+fn parse_or<T: FromStr + Default>(input: &str, value: T) -> T {
+    input.parse().unwrap_or(value)
+}
+
+#[derive(Default)]
+pub struct FetchOptions {
+    pub retries: u8,
+    pub verbose: bool,
+}
+
+#[named(options = FetchOptions, defaults(timeout = 30))]
+fn fetch(path: &str, options: FetchOptions, timeout: u32) -> String {
+    format!(
+        "path=[{}], retries=[{}], verbose=[{}], timeout=[{}]",
+        path, options.retries, options.verbose, timeout
+    )
+}
+
+pub struct Sums {
+    a: u8,
+    b: u8,
+    c: u8,
+}
+
+#[macro_use]
+mod shapes {
+    use named::named;
+
+    #[named(defaults(height = 1))]
+    pub fn area(width: u32, height: u32) -> u32 {
+        width * height
+    }
+}
+
+use shapes::*;
+
+#[named_trait]
+trait Counter {
+    #[named(defaults(by = 1))]
+    fn add(&mut self, by: u32) -> u32;
+}
+
+struct Total(u32);
+
+impl Counter for Total {
+    fn add(&mut self, by: u32) -> u32 {
+        self.0 += by;
+        self.0
+    }
+}
+
+// Shares its method's name with `Counter` and with a function, which method syntax tells apart by the trait's name.
+#[named_trait]
+trait Tally {
+    #[named(macro_name = tally, defaults(times = 1))]
+    fn add(&mut self, item: &'static str, times: usize) -> usize;
+}
+
+#[named(defaults(b = 1))]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+struct Items(Vec<&'static str>);
+
+impl Tally for Items {
+    fn add(&mut self, item: &'static str, times: usize) -> usize {
+        self.0.extend(std::iter::repeat(item).take(times));
+        self.0.len()
+    }
+}
+
+fn main() {
+    // Arguments may be given in any order.
+    assert_eq!(named::call!(sum(a = 1)), 6);
+    assert_eq!(named::call!(sum(c = 0, a = 1)), 3);
+    assert_eq!(named::call!(sum(c = 0, b = 0, a = 1,)), 1);
+
+    // Generic arguments are given in a turbofish, by name or by position.
+    assert_eq!(named::call!(parse_or::<T = u8>(input = "x")), 0);
+    assert_eq!(named::call!(parse_or::<u8>(value = 7, input = "x")), 7);
+    let parsed: u16 = named::call!(parse_or(input = "300"));
+    assert_eq!(parsed, 300);
+
+    // Options struct fields may be mixed in with the function's own arguments.
+    assert_eq!(
+        named::call!(fetch(verbose = true, path = "/", retries = 2)),
+        "path=[/], retries=[2], verbose=[true], timeout=[30]"
+    );
+
+    // Unspecified arguments can be taken from a struct.
+    let base = Sums { a: 10, b: 20, c: 30 };
+    assert_eq!(named::call!(sum(c = 0, ..base)), 30);
+
+    assert_eq!(named::call!(area(width = 2)), 2);
+
+    // Method syntax passes the receiver as `self`.
+    let mut total = Total(0);
+    assert_eq!(named::call!(total.Counter::add()), 1);
+    assert_eq!(named::call!(total.Counter::add(by = 2)), 3);
+    assert_eq!(named::call!(Counter_add(by = 3, self = &mut total)), 6);
+
+    let mut items = Items(Vec::new());
+    assert_eq!(named::call!(items.Tally::add(item = "x", times = 2)), 2);
+    assert_eq!(named::call!(add(a = 1)), 2);
+}
@@ -0,0 +1,28 @@
+use named::named;
+
+#[named(defaults(port = 80, cert = "cert.pem", verbose = false))]
+fn connect(
+    host: &str,
+    #[cfg(all())] port: u16,
+    #[cfg(not(all()))] cert: &str,
+    verbose: bool,
+) -> String {
+    format!("{}:{} {}", host, port, verbose)
+}
+
+#[named]
+fn enabled(#[cfg(all())] a: u8, #[cfg(all())] #[cfg(not(any()))] b: u8) -> u8 {
+    a + b
+}
+
+#[named]
+fn disabled(#[cfg(any())] a: u8) -> u8 {
+    7
+}
+
+fn main() {
+    assert_eq!(connect!(host = "localhost"), "localhost:80 false");
+    assert_eq!(connect!(host = "localhost", port = 8080, verbose = true), "localhost:8080 true");
+    assert_eq!(enabled!(a = 1, b = 2), 3);
+    assert_eq!(disabled!(), 7);
+}
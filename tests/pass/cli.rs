@@ -0,0 +1,76 @@
+use clap::error::ErrorKind;
+use clap::{Args, Command, FromArgMatches};
+use named::named;
+
+#[named(cli, docs(name = "Who to greet."), defaults(greeting = "Hello".to_owned(), times = 1))]
+fn greet(name: String, greeting: String, times: usize, shout: bool, suffix: Option<char>) -> String {
+    let greeting = format!("{}, {}!", greeting, name).repeat(times);
+    let greeting = if shout {
+        greeting.to_uppercase()
+    } else {
+        greeting
+    };
+    match suffix {
+        Some(suffix) => format!("{}{}", greeting, suffix),
+        None => greeting,
+    }
+}
+
+#[named(cli, serde, defaults(count = 3))]
+fn count(count: u8, #[cfg(any())] verbose: bool) -> u8 {
+    count
+}
+
+fn parse<T: Args + FromArgMatches>(args: &[&str]) -> Result<T, ErrorKind> {
+    let command = T::augment_args(Command::new("test").no_binary_name(true));
+    let matches = command
+        .try_get_matches_from(args)
+        .map_err(|err| err.kind())?;
+    T::from_arg_matches(&matches).map_err(|err| err.kind())
+}
+
+fn main() {
+    let args: GreetArgs = parse(&["--name", "World"]).unwrap();
+    assert_eq!(args.greeting, "Hello");
+    assert_eq!(args.call(), "Hello, World!");
+
+    let args: GreetArgs = parse(&[
+        "--shout", "--times", "2", "--name", "you", "--greeting", "Hi", "--suffix", "?",
+    ])
+    .unwrap();
+    assert_eq!(args.call(), "HI, YOU!HI, YOU!?");
+
+    assert_eq!(
+        parse::<GreetArgs>(&[]).err().unwrap(),
+        ErrorKind::MissingRequiredArgument
+    );
+    assert_eq!(
+        parse::<GreetArgs>(&["--name", "World", "--volume", "11"]).err().unwrap(),
+        ErrorKind::UnknownArgument
+    );
+    assert_eq!(
+        parse::<GreetArgs>(&["--name", "World", "--times", "many"]).err().unwrap(),
+        ErrorKind::ValueValidation
+    );
+
+    let mut help = GreetArgs::augment_args(Command::new("greet"));
+    let help = help.render_help().to_string();
+    assert!(help.contains("Usage: greet [OPTIONS] --name <NAME>"), "{}", help);
+    assert!(help.contains("Who to greet."), "{}", help);
+    assert!(help.contains("[default: Hello]"), "{}", help);
+    assert!(help.contains("[default: 1]"), "{}", help);
+
+    let mut args: CountArgs = parse(&[]).unwrap();
+    assert_eq!(args.count, 3);
+    let matches = CountArgs::augment_args_for_update(Command::new("count"))
+        .try_get_matches_from(["count", "--count", "5"])
+        .unwrap();
+    args.update_from_arg_matches(&matches).unwrap();
+    assert_eq!(args.count, 5);
+
+    assert_eq!(count_main_from(["count"]), 3);
+    assert_eq!(count_main_from(["count", "--count", "4"]), 4);
+    assert_eq!(greet_main_from(["greet", "--name", "World", "--shout"]), "HELLO, WORLD!");
+    assert_eq!(count!(), 3);
+    assert_eq!(greet!(name = "macro".to_owned(), shout = false, suffix = None), "Hello, macro!");
+}
@@ -0,0 +1,30 @@
+#![deny(warnings)]
+
+fn main() {
+    let offset = 10;
+    named::closure!(add = |a: u8, b: u8 = 2| a + b + offset);
+    assert_eq!(add!(a = 1), 13);
+    assert_eq!(add!(a = 1, b = 3), 14);
+    assert_eq!(add(1, 1), 12);
+
+    // Types may be inferred, and defaults may be any expression.
+    named::closure!(pair = |left = offset * 2, right: &str = "b"| format!("{}{}", left, right));
+    assert_eq!(pair!(), "20b");
+    assert_eq!(pair!(left = 1), "1b");
+    assert_eq!(pair!(right = "c"), "20c");
+
+    // Closures may capture by move, and need not take any arguments.
+    let name = String::from("named");
+    named::closure!(greeting = move || format!("Hello, {}!", name));
+    assert_eq!(greeting!(), "Hello, named!");
+
+    // Closures may mutate their captures.
+    let mut total = 0;
+    named::closure!(mut accumulate = |amount: u32 = 1| {
+        total += amount;
+        total
+    });
+    accumulate!();
+    accumulate!(amount = 5);
+    assert_eq!(total, 6);
+}
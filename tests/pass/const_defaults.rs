@@ -0,0 +1,24 @@
+use named::named;
+
+const LIMIT: usize = 8;
+
+const fn doubled(value: usize) -> usize {
+    value * 2
+}
+
+#[named(const_defaults, defaults(limit = doubled(LIMIT), name = "default"))]
+fn describe(limit: usize, name: &str) -> String {
+    format!("{}: {}", name, limit)
+}
+
+#[named(const_defaults, defaults(value = None))]
+fn unwrap_or_zero<T: Into<u64>>(value: Option<T>) -> u64 {
+    value.map_or(0, Into::into)
+}
+
+fn main() {
+    assert_eq!(describe!(), "default: 16");
+    assert_eq!(describe!(limit = 1), "default: 1");
+    assert_eq!(unwrap_or_zero!(<T = u8>), 0);
+    assert_eq!(unwrap_or_zero!(value = Some(3u32)), 3);
+}
@@ -0,0 +1,48 @@
+use named::{named, named_trait};
+
+#[named(macro_name = make_vec, defaults(len = 2))]
+fn vec(len: usize, value: u8) -> Vec<u8> {
+    std::iter::repeat(value).take(len).collect()
+}
+
+// An item which would collide with the default implementation name.
+#[allow(dead_code)]
+fn __widget() {}
+
+#[named(macro_name = make_widget, impl_name = widget_impl, defaults(size = 1))]
+fn widget(size: u8) -> u8 {
+    size
+}
+
+#[named(keep_name, macro_name = sum)]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[named_trait]
+pub trait Shape {
+    #[named(macro_name = shape_area, defaults(scale = 1))]
+    fn area(&self, scale: u32) -> u32;
+}
+
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self, scale: u32) -> u32 {
+        self.0 * self.0 * scale
+    }
+}
+
+fn main() {
+    // The standard vec! macro is still usable.
+    assert_eq!(vec![1, 1], make_vec!(value = 1));
+    assert_eq!(vec![3, 3, 3], make_vec!(len = 3, value = 3));
+
+    assert_eq!(1, make_widget!());
+    assert_eq!(2, widget_impl(2));
+
+    assert_eq!(3, sum!(a = 1, b = 2));
+    assert_eq!(3, add(1, 2));
+
+    assert_eq!(8, shape_area!(self = &Square(2), scale = 2));
+}
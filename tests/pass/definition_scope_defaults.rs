@@ -0,0 +1,48 @@
+#[macro_use]
+mod shapes {
+    use named::named;
+
+    const DEFAULT_SIDES: u32 = 4;
+
+    fn default_name() -> String {
+        String::from("square")
+    }
+
+    #[named(defaults(sides = DEFAULT_SIDES, name = default_name()))]
+    pub fn describe(sides: u32, name: String) -> String {
+        format!("{} has {} sides", name, sides)
+    }
+
+    #[named(defaults(value = T::default()))]
+    pub fn boxed<T: Default>(value: T) -> Box<T> {
+        Box::new(value)
+    }
+
+    #[named(defaults(extra = DEFAULT_SIDES))]
+    pub const fn add(base: u32, extra: u32) -> u32 {
+        base + extra
+    }
+
+    #[named(defaults(label = "shape"))]
+    pub fn label(label: &str) -> &str {
+        label
+    }
+}
+
+// This brings the functions into scope, but not the private items which their defaults use.
+use shapes::*;
+
+const ADDED: u32 = add!(base = 1);
+
+fn main() {
+    assert_eq!(describe!(), "square has 4 sides");
+    assert_eq!(describe!(sides = 3, name = String::from("triangle")), "triangle has 3 sides");
+
+    assert_eq!(*boxed!(<T = u8>), 0);
+    assert_eq!(*boxed!(T = String), "");
+    let inferred: Box<Vec<u8>> = boxed!();
+    assert!(inferred.is_empty());
+
+    assert_eq!(ADDED, 5);
+    assert_eq!(label!(), "shape");
+}
@@ -0,0 +1,57 @@
+#![deny(warnings)]
+
+use named::named;
+
+/// Joins a list of words.
+///
+/// The separator goes between each pair of words, but not at either end.
+#[named(
+    defaults(separator = ", "),
+    docs(words = "The words to join.", separator = "Goes between each word.")
+)]
+fn join(words: &[&str], separator: &str) -> String {
+    words.join(separator)
+}
+
+/// Quotes some text.
+#[named(defaults(open = "( ", close = " ) ,"))]
+fn quote(text: &str, open: &str, close: &str) -> String {
+    format!("{}{}{}", open, text, close)
+}
+
+/// Documented functions whose documentation is moved to the macro must not trip any lints.
+#[named(keep_name, defaults(n = 1))]
+fn increment(value: u32, n: u32) -> u32 {
+    value + n
+}
+
+#[derive(Default)]
+struct Padding {
+    width: usize,
+}
+
+/// Pads a string.
+#[named(options = Padding, docs(padding = "How much padding to add."))]
+fn pad(s: &str, padding: Padding) -> String {
+    format!("{:>width$}", s, width = padding.width)
+}
+
+named::wrap!(
+    /// Repeats a string.
+    fn repeat = str::repeat(s: &str, n: usize) -> String; defaults(n = 2), docs(s = "The string to repeat.")
+);
+
+fn main() {
+    assert_eq!(join!(words = &["a", "b"]), "a, b");
+    assert_eq!(join!(words = &["a", "b"], separator = "-"), "a-b");
+    assert_eq!(increment!(value = 1), 2);
+    assert_eq!(increment(1, 2), 3);
+    assert_eq!(pad!(s = "a", width = 3), "  a");
+    assert_eq!(repeat!(s = "ab"), "abab");
+
+    // Defaults are documented with the same rendering as their signature gives, which keeps literals as they're written.
+    assert_eq!(quote!(text = "a"), "( a ) ,");
+    let quote = named::signature!(quote);
+    assert_eq!(quote.param("open").unwrap().default, Some("\"( \""));
+    assert_eq!(quote.param("close").unwrap().default, Some("\" ) ,\""));
+}
@@ -0,0 +1,153 @@
+use named::{named, named_trait};
+use std::cell::RefCell;
+
+thread_local! {
+    static EVALUATED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn record<T>(name: &'static str, value: T) -> T {
+    EVALUATED.with(|evaluated| evaluated.borrow_mut().push(name));
+    value
+}
+
+fn evaluated() -> Vec<&'static str> {
+    EVALUATED.with(|evaluated| evaluated.borrow_mut().drain(..).collect())
+}
+
+#[named(defaults(b = 2, c = 3))]
+fn sum(a: u8, b: u8, c: u8) -> u8 {
+    a + b + c
+}
+
+#[derive(Default)]
+pub struct FetchOptions {
+    pub retries: u8,
+    pub verbose: bool,
+}
+
+#[named(options = FetchOptions, defaults(timeout = 30))]
+fn fetch(path: &str, options: FetchOptions, timeout: u32) -> String {
+    format!("{}:{}:{}:{}", path, options.retries, options.verbose, timeout)
+}
+
+pub struct FetchBase {
+    path: &'static str,
+    options: FetchOptions,
+    timeout: u32,
+}
+
+fn fetch_base() -> FetchBase {
+    FetchBase {
+        path: "/base",
+        options: FetchOptions::default(),
+        timeout: 1,
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Sums {
+    a: u8,
+    b: u8,
+    c: u8,
+}
+
+#[named]
+fn push(values: &mut Vec<String>, value: &str) -> usize {
+    values.push(value.to_owned());
+    values.len()
+}
+
+#[derive(Default)]
+pub struct Scale {
+    pub factor: u8,
+}
+
+#[named(options = Scale, defaults(offset = 2))]
+fn apply(f: impl Fn(u8) -> u8, scale: Scale, offset: u8) -> u8 {
+    f(scale.factor) + offset
+}
+
+#[named(defaults(b = 2))]
+fn apply2(f: impl Fn(u8) -> u8, b: u8) -> u8 {
+    f(b)
+}
+
+#[named_trait]
+trait Counter {
+    #[named(macro_name = scale, defaults(plus = 0))]
+    fn scale(&mut self, by: u32, plus: u32) -> u32;
+}
+
+struct Total(u32);
+
+impl Counter for Total {
+    fn scale(&mut self, by: u32, plus: u32) -> u32 {
+        self.0 = self.0 * by + plus;
+        self.0
+    }
+}
+
+fn main() {
+    // Fields and arguments are evaluated in the order they're given, not the order they're passed in.
+    assert_eq!(
+        fetch!(
+            verbose = record("verbose", true),
+            path = record("path", "/"),
+            retries = record("retries", 2),
+            timeout = record("timeout", 5),
+        ),
+        "/:2:true:5"
+    );
+    assert_eq!(evaluated(), ["verbose", "path", "retries", "timeout"]);
+
+    // A spread struct is evaluated wherever it's given.
+    let base = Sums { a: 10, b: 20, c: 30 };
+    assert_eq!(sum!(c = record("c", 0), ..record("base", base)), 30);
+    assert_eq!(evaluated(), ["c", "base"]);
+    assert_eq!(sum!(..record("base", base), c = record("c", 0)), 30);
+    assert_eq!(evaluated(), ["base", "c"]);
+    assert_eq!(
+        fetch!(retries = record("retries", 1), ..record("base", fetch_base())),
+        "/base:1:false:1"
+    );
+    assert_eq!(evaluated(), ["retries", "base"]);
+
+    assert_eq!(
+        named::call!(sum(c = record("c", 1), ..record("base", base), a = record("a", 1))),
+        22
+    );
+    assert_eq!(evaluated(), ["c", "base", "a"]);
+    assert_eq!(
+        named::call!(sum(c = record("c", 1), b = record("b", 1), a = record("a", 1))),
+        3
+    );
+    assert_eq!(evaluated(), ["c", "b", "a"]);
+
+    // Temporaries borrowed by an argument live until the call is complete, and mutable borrows are passed through.
+    let mut values = Vec::new();
+    assert_eq!(push!(values = &mut values, value = &String::from("a")), 1);
+    assert_eq!(named::call!(push(value = &String::from("b"), values = &mut values)), 2);
+    assert_eq!(values, ["a", "b"]);
+
+    // Values which are passed straight in, and closures, which are never bound, infer their types from the parameters.
+    assert_eq!(apply!(f = |x| x.pow(2)), 2);
+    assert_eq!(apply!(f = |x| x + 1, factor = 3, offset = 0), 4);
+    assert_eq!(apply2!(f = |x| x.pow(2)), 4);
+    assert_eq!(named::call!(apply2(b = 3, f = |x| x.pow(2))), 9);
+    assert_eq!(
+        named::call!(apply(offset = record("offset", 1), factor = 2, f = |x| x * 5)),
+        11
+    );
+    assert_eq!(evaluated(), ["offset"]);
+
+    // A receiver is evaluated before the arguments, and one which is a place is borrowed by the call.
+    let mut total = Total(1);
+    assert_eq!(
+        named::call!(record("receiver", &mut total).Counter::scale(plus = record("plus", 1), by = record("by", 2))),
+        3
+    );
+    assert_eq!(evaluated(), ["receiver", "plus", "by"]);
+    assert_eq!(named::call!(total.Counter::scale(plus = record("plus", 1), by = record("by", 2))), 7);
+    assert_eq!(evaluated(), ["plus", "by"]);
+    assert_eq!(scale!(self = &mut total, by = 1), 7);
+}
@@ -0,0 +1,67 @@
+use named::named;
+use std::mem::MaybeUninit;
+
+#[named(ffi, serde, docs(width = "How many columns to draw."), defaults(height = 1, fill = b'#'))]
+pub fn area(width: u32, height: u32, fill: u8, #[cfg(any())] depth: u32) -> u64 {
+    let _ = fill;
+    u64::from(width) * u64::from(height)
+}
+
+#[named(ffi, defaults(scale = 1.0))]
+pub unsafe fn scale(value: *const f64, scale: f64) -> f64 {
+    *value * scale
+}
+
+#[named(ffi)]
+pub fn nothing() {}
+
+#[named(ffi, defaults(offset = 0))]
+pub fn shifted(value: &u32, offset: u32) -> u32 {
+    value + offset
+}
+
+fn main() {
+    // As a C caller would: default the struct, then set the arguments without defaults.
+    let mut args = MaybeUninit::<AreaArgs>::uninit();
+    let area = unsafe {
+        area_args_default(args.as_mut_ptr());
+        (*args.as_mut_ptr()).width = 3;
+        area_call(args.as_ptr())
+    };
+    assert_eq!(area, 3);
+    assert_eq!(area, area!(width = 3));
+
+    let args = AreaArgs {
+        width: 3,
+        height: 4,
+        fill: b'*',
+    };
+    assert_eq!(unsafe { area_call(&args) }, 12);
+    let args: AreaArgs = serde_json::from_str(r#"{ "width": 5 }"#).unwrap();
+    assert_eq!(unsafe { area_call(&args) }, 5);
+
+    let value = 2.0;
+    let mut args = MaybeUninit::<ScaleArgs>::uninit();
+    let scaled = unsafe {
+        scale_args_default(args.as_mut_ptr());
+        (*args.as_mut_ptr()).value = &value;
+        scale_call(args.as_ptr())
+    };
+    assert_eq!(scaled, 2.0);
+    assert_eq!(unsafe { scale!(value = &value, scale = 3.0) }, 6.0);
+
+    let mut args = MaybeUninit::<NothingArgs>::uninit();
+    unsafe {
+        nothing_args_default(args.as_mut_ptr());
+        nothing_call(args.as_ptr());
+    }
+    nothing!();
+
+    // Lifetimes which the arguments use need no marker field, which C callers would have to mirror.
+    let args = ShiftedArgs {
+        value: &3,
+        offset: 2,
+    };
+    assert_eq!(unsafe { shifted_call(&args) }, 5);
+    assert_eq!(shifted!(value = &3), 3);
+}
@@ -0,0 +1,66 @@
+use named::{named, named_trait};
+use std::str::FromStr;
+
+#[named]
+fn parse<T: FromStr>(input: &str) -> Option<T> {
+    input.parse().ok()
+}
+
+#[named(types(T = String), defaults(count = 1))]
+fn make<T: Default + Extend<char>>(c: char, count: usize) -> T {
+    let mut made = T::default();
+    made.extend(std::iter::repeat(c).take(count));
+    made
+}
+
+#[named(types(N = 2))]
+fn repeat<const N: usize>(value: u8) -> [u8; N] {
+    [value; N]
+}
+
+#[named]
+fn pair<A: Default, B: Default>(first: A) -> (A, B) {
+    (first, B::default())
+}
+
+#[named_trait]
+trait Converter {
+    fn convert<T: From<u8>>(&self, value: u8) -> T;
+}
+
+struct Identity;
+
+impl Converter for Identity {
+    fn convert<T: From<u8>>(&self, value: u8) -> T {
+        T::from(value)
+    }
+}
+
+fn main() {
+    // Generic arguments can be given in brackets, or by name, before the arguments.
+    assert_eq!(parse!(<T = u32>, input = "7"), Some(7u32));
+    assert_eq!(parse!(T = i8, input = "-1"), Some(-1i8));
+
+    // Or left to be inferred.
+    let inferred: Option<f64> = parse!(input = "1.5");
+    assert_eq!(inferred, Some(1.5));
+
+    // Defaults are used when no generic argument is given.
+    assert_eq!(make!(c = 'a', count = 2), "aa");
+    assert_eq!(make!(<T = Vec<char>>, c = 'b'), vec!['b']);
+
+    // Const generics work too.
+    assert_eq!(repeat!(value = 1), [1, 1]);
+    assert_eq!(repeat!(<N = 3>, value = 1), [1, 1, 1]);
+    assert_eq!(repeat!(N = 1 + 3, value = 1), [1, 1, 1, 1]);
+
+    // Generic arguments can be given in any order.
+    assert_eq!(pair!(<B = u8, A = u16>, first = 1), (1u16, 0u8));
+    assert_eq!(pair!(B = String, first = 1u8), (1u8, String::new()));
+    assert_eq!(pair!(<A = Vec<u8>, B = Option<Vec<u8>>,>, first = vec![1]), (vec![1], None));
+
+    assert_eq!(Converter_convert!(<T = u64>, self = &Identity, value = 3), 3u64);
+
+    // Calls can be nested.
+    assert_eq!(parse!(<T = u8>, input = &parse!(<T = String>, input = "4").unwrap()), Some(4));
+}
@@ -0,0 +1,24 @@
+use named::named;
+
+#[named(keep_name, defaults(b = false))]
+fn or(a: bool, b: bool) -> bool {
+    a || b
+}
+
+fn apply(f: fn(bool, bool) -> bool) -> bool {
+    f(false, true)
+}
+
+fn main() {
+    // The macro
+    assert!(or!(a = true));
+    assert!(!or!(a = false));
+    assert!(or!(a = false, b = true));
+
+    // The original function, called positionally
+    assert!(or(true, false));
+    assert!(!or(false, false));
+
+    // The original function, as a function pointer
+    assert!(apply(or));
+}
@@ -0,0 +1,407 @@
+use named::named;
+
+// Wide signatures must expand within the default recursion limit.
+#[named(defaults(
+    a1 = 1,
+    a2 = 2,
+    a3 = 3,
+    a4 = 4,
+    a5 = 5,
+    a6 = 6,
+    a7 = 7,
+    a8 = 8,
+    a9 = 9,
+    a10 = 10,
+    a11 = 11,
+    a12 = 12,
+    a13 = 13,
+    a14 = 14,
+    a15 = 15,
+    a16 = 16,
+    a17 = 17,
+    a18 = 18,
+    a19 = 19,
+    a20 = 20,
+    a21 = 21,
+    a22 = 22,
+    a23 = 23,
+    a24 = 24,
+    a25 = 25,
+    a26 = 26,
+    a27 = 27,
+    a28 = 28,
+    a29 = 29,
+    a30 = 30,
+    a31 = 31,
+    a32 = 32,
+    a33 = 33,
+    a34 = 34,
+    a35 = 35,
+    a36 = 36,
+    a37 = 37,
+    a38 = 38,
+    a39 = 39,
+    a40 = 40,
+    a41 = 41,
+    a42 = 42,
+    a43 = 43,
+    a44 = 44,
+    a45 = 45,
+    a46 = 46,
+    a47 = 47,
+    a48 = 48,
+    a49 = 49,
+    a50 = 50,
+    a51 = 51,
+    a52 = 52,
+    a53 = 53,
+    a54 = 54,
+    a55 = 55,
+    a56 = 56,
+    a57 = 57,
+    a58 = 58,
+    a59 = 59,
+    a60 = 60,
+    a61 = 61,
+    a62 = 62,
+    a63 = 63
+))]
+fn sum(
+    a0: u64,
+    a1: u64,
+    a2: u64,
+    a3: u64,
+    a4: u64,
+    a5: u64,
+    a6: u64,
+    a7: u64,
+    a8: u64,
+    a9: u64,
+    a10: u64,
+    a11: u64,
+    a12: u64,
+    a13: u64,
+    a14: u64,
+    a15: u64,
+    a16: u64,
+    a17: u64,
+    a18: u64,
+    a19: u64,
+    a20: u64,
+    a21: u64,
+    a22: u64,
+    a23: u64,
+    a24: u64,
+    a25: u64,
+    a26: u64,
+    a27: u64,
+    a28: u64,
+    a29: u64,
+    a30: u64,
+    a31: u64,
+    a32: u64,
+    a33: u64,
+    a34: u64,
+    a35: u64,
+    a36: u64,
+    a37: u64,
+    a38: u64,
+    a39: u64,
+    a40: u64,
+    a41: u64,
+    a42: u64,
+    a43: u64,
+    a44: u64,
+    a45: u64,
+    a46: u64,
+    a47: u64,
+    a48: u64,
+    a49: u64,
+    a50: u64,
+    a51: u64,
+    a52: u64,
+    a53: u64,
+    a54: u64,
+    a55: u64,
+    a56: u64,
+    a57: u64,
+    a58: u64,
+    a59: u64,
+    a60: u64,
+    a61: u64,
+    a62: u64,
+    a63: u64,
+) -> u64 {
+    a0 + a1
+        + a2
+        + a3
+        + a4
+        + a5
+        + a6
+        + a7
+        + a8
+        + a9
+        + a10
+        + a11
+        + a12
+        + a13
+        + a14
+        + a15
+        + a16
+        + a17
+        + a18
+        + a19
+        + a20
+        + a21
+        + a22
+        + a23
+        + a24
+        + a25
+        + a26
+        + a27
+        + a28
+        + a29
+        + a30
+        + a31
+        + a32
+        + a33
+        + a34
+        + a35
+        + a36
+        + a37
+        + a38
+        + a39
+        + a40
+        + a41
+        + a42
+        + a43
+        + a44
+        + a45
+        + a46
+        + a47
+        + a48
+        + a49
+        + a50
+        + a51
+        + a52
+        + a53
+        + a54
+        + a55
+        + a56
+        + a57
+        + a58
+        + a59
+        + a60
+        + a61
+        + a62
+        + a63
+}
+
+struct Sums {
+    a0: u64,
+    a1: u64,
+    a2: u64,
+    a3: u64,
+    a4: u64,
+    a5: u64,
+    a6: u64,
+    a7: u64,
+    a8: u64,
+    a9: u64,
+    a10: u64,
+    a11: u64,
+    a12: u64,
+    a13: u64,
+    a14: u64,
+    a15: u64,
+    a16: u64,
+    a17: u64,
+    a18: u64,
+    a19: u64,
+    a20: u64,
+    a21: u64,
+    a22: u64,
+    a23: u64,
+    a24: u64,
+    a25: u64,
+    a26: u64,
+    a27: u64,
+    a28: u64,
+    a29: u64,
+    a30: u64,
+    a31: u64,
+    a32: u64,
+    a33: u64,
+    a34: u64,
+    a35: u64,
+    a36: u64,
+    a37: u64,
+    a38: u64,
+    a39: u64,
+    a40: u64,
+    a41: u64,
+    a42: u64,
+    a43: u64,
+    a44: u64,
+    a45: u64,
+    a46: u64,
+    a47: u64,
+    a48: u64,
+    a49: u64,
+    a50: u64,
+    a51: u64,
+    a52: u64,
+    a53: u64,
+    a54: u64,
+    a55: u64,
+    a56: u64,
+    a57: u64,
+    a58: u64,
+    a59: u64,
+    a60: u64,
+    a61: u64,
+    a62: u64,
+    a63: u64,
+}
+
+fn main() {
+    assert_eq!(sum!(a0 = 0), 2016);
+    assert_eq!(sum!(a0 = 100, a31 = 0, a63 = 0), 2016 + 100 - 31 - 63);
+    assert_eq!(
+        sum!(
+            a0 = 1,
+            a1 = 1,
+            a2 = 1,
+            a3 = 1,
+            a4 = 1,
+            a5 = 1,
+            a6 = 1,
+            a7 = 1,
+            a8 = 1,
+            a9 = 1,
+            a10 = 1,
+            a11 = 1,
+            a12 = 1,
+            a13 = 1,
+            a14 = 1,
+            a15 = 1,
+            a16 = 1,
+            a17 = 1,
+            a18 = 1,
+            a19 = 1,
+            a20 = 1,
+            a21 = 1,
+            a22 = 1,
+            a23 = 1,
+            a24 = 1,
+            a25 = 1,
+            a26 = 1,
+            a27 = 1,
+            a28 = 1,
+            a29 = 1,
+            a30 = 1,
+            a31 = 1,
+            a32 = 1,
+            a33 = 1,
+            a34 = 1,
+            a35 = 1,
+            a36 = 1,
+            a37 = 1,
+            a38 = 1,
+            a39 = 1,
+            a40 = 1,
+            a41 = 1,
+            a42 = 1,
+            a43 = 1,
+            a44 = 1,
+            a45 = 1,
+            a46 = 1,
+            a47 = 1,
+            a48 = 1,
+            a49 = 1,
+            a50 = 1,
+            a51 = 1,
+            a52 = 1,
+            a53 = 1,
+            a54 = 1,
+            a55 = 1,
+            a56 = 1,
+            a57 = 1,
+            a58 = 1,
+            a59 = 1,
+            a60 = 1,
+            a61 = 1,
+            a62 = 1,
+            a63 = 1
+        ),
+        64
+    );
+
+    let base = Sums {
+        a0: 2,
+        a1: 2,
+        a2: 2,
+        a3: 2,
+        a4: 2,
+        a5: 2,
+        a6: 2,
+        a7: 2,
+        a8: 2,
+        a9: 2,
+        a10: 2,
+        a11: 2,
+        a12: 2,
+        a13: 2,
+        a14: 2,
+        a15: 2,
+        a16: 2,
+        a17: 2,
+        a18: 2,
+        a19: 2,
+        a20: 2,
+        a21: 2,
+        a22: 2,
+        a23: 2,
+        a24: 2,
+        a25: 2,
+        a26: 2,
+        a27: 2,
+        a28: 2,
+        a29: 2,
+        a30: 2,
+        a31: 2,
+        a32: 2,
+        a33: 2,
+        a34: 2,
+        a35: 2,
+        a36: 2,
+        a37: 2,
+        a38: 2,
+        a39: 2,
+        a40: 2,
+        a41: 2,
+        a42: 2,
+        a43: 2,
+        a44: 2,
+        a45: 2,
+        a46: 2,
+        a47: 2,
+        a48: 2,
+        a49: 2,
+        a50: 2,
+        a51: 2,
+        a52: 2,
+        a53: 2,
+        a54: 2,
+        a55: 2,
+        a56: 2,
+        a57: 2,
+        a58: 2,
+        a59: 2,
+        a60: 2,
+        a61: 2,
+        a62: 2,
+        a63: 2,
+    };
+    assert_eq!(sum!(..base, a63 = 0), 126);
+}
@@ -0,0 +1,60 @@
+use named::named_module;
+
+#[named_module]
+mod geometry {
+    pub fn unit_square() -> u32 {
+        // Defined below, but the macro is available throughout the module.
+        area!(width = 1)
+    }
+
+    #[defaults(height = 1)]
+    pub fn area(width: u32, height: u32) -> u32 {
+        width * height
+    }
+
+    #[named(defaults(depth = 2))]
+    pub fn volume(width: u32, height: u32, depth: u32) -> u32 {
+        area!(width = width, height = height) * depth
+    }
+
+    // Functions without an attribute are left alone.
+    pub fn perimeter(width: u32, height: u32) -> u32 {
+        2 * (width + height)
+    }
+
+    pub mod nested {
+        use super::*;
+
+        pub fn double_unit_cube() -> u32 {
+            volume!(width = 1, height = 1)
+        }
+    }
+}
+
+#[named_module(all_pub)]
+mod text {
+    pub fn shout(s: &str) -> String {
+        s.to_uppercase()
+    }
+
+    #[defaults(times = 2)]
+    pub fn repeat(s: &str, times: usize) -> String {
+        s.repeat(times)
+    }
+
+    fn private(s: &str) -> String {
+        s.to_owned()
+    }
+
+    // Only `pub` functions are processed, so this keeps its name.
+    pub(crate) fn loud_echo(s: &str) -> String {
+        repeat!(s = &shout!(s = &private(s)))
+    }
+}
+
+fn main() {
+    assert_eq!(1, geometry::unit_square());
+    assert_eq!(2, geometry::nested::double_unit_cube());
+    assert_eq!(6, geometry::perimeter(1, 2));
+    assert_eq!("HIHI", &text::loud_echo("hi"));
+}
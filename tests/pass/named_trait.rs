@@ -0,0 +1,89 @@
+use named::named_trait;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Red,
+    Blue,
+}
+
+pub const RED: Color = Color::Red;
+
+#[named_trait]
+pub trait Renderer {
+    #[defaults(color = RED, width = 1)]
+    fn draw(&self, color: Color, width: u8) -> String;
+
+    #[named(defaults(times = 1))]
+    fn repeat(&mut self, times: usize) -> usize {
+        self.count(times)
+    }
+
+    fn count(&mut self, times: usize) -> usize;
+
+    fn name(self) -> String
+    where
+        Self: Sized;
+}
+
+struct Canvas {
+    calls: usize,
+}
+
+impl Renderer for Canvas {
+    fn draw(&self, color: Color, width: u8) -> String {
+        format!("canvas color=[{:?}], width=[{}]", color, width)
+    }
+
+    fn count(&mut self, times: usize) -> usize {
+        self.calls += times;
+        self.calls
+    }
+
+    fn name(self) -> String {
+        "canvas".to_owned()
+    }
+}
+
+struct Terminal;
+
+impl Renderer for Terminal {
+    fn draw(&self, color: Color, width: u8) -> String {
+        format!("terminal color=[{:?}], width=[{}]", color, width)
+    }
+
+    fn count(&mut self, times: usize) -> usize {
+        times
+    }
+
+    fn name(self) -> String {
+        "terminal".to_owned()
+    }
+}
+
+fn draw_generic<R: Renderer>(renderer: &R) -> String {
+    Renderer_draw!(self = renderer, width = 3)
+}
+
+fn main() {
+    // Dynamic dispatch
+    let renderers: Vec<Box<dyn Renderer>> = vec![Box::new(Canvas { calls: 0 }), Box::new(Terminal)];
+    assert_eq!(
+        "canvas color=[Red], width=[1]",
+        &Renderer_draw!(self = &*renderers[0])
+    );
+    assert_eq!(
+        "terminal color=[Blue], width=[2]",
+        &Renderer_draw!(self = &*renderers[1], color = Color::Blue, width = 2)
+    );
+
+    // Static dispatch
+    assert_eq!("canvas color=[Red], width=[3]", &draw_generic(&Canvas { calls: 0 }));
+    assert_eq!("terminal color=[Red], width=[3]", &draw_generic(&Terminal));
+
+    // Provided methods, and other receivers
+    let mut canvas = Canvas { calls: 0 };
+    assert_eq!(1, Renderer_repeat!(self = &mut canvas));
+    assert_eq!(3, Renderer_repeat!(self = &mut canvas, times = 2));
+    assert_eq!(5, Renderer_count!(self = &mut canvas, times = 2));
+    assert_eq!("canvas", &Renderer_name!(self = canvas));
+}
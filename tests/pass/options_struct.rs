@@ -0,0 +1,59 @@
+use named::named;
+
+#[derive(Default)]
+pub struct FetchOptions {
+    pub retries: u8,
+    pub verbose: bool,
+}
+
+#[named(options = FetchOptions, defaults(timeout = 30))]
+fn fetch(path: &str, options: FetchOptions, timeout: u32) -> String {
+    format!(
+        "path=[{}], retries=[{}], verbose=[{}], timeout=[{}]",
+        path, options.retries, options.verbose, timeout
+    )
+}
+
+fn main() {
+    // No fields specified
+    assert_eq!(
+        "path=[/], retries=[0], verbose=[false], timeout=[30]",
+        &fetch!(path = "/")
+    );
+    assert_eq!(
+        "path=[/], retries=[0], verbose=[false], timeout=[5]",
+        &fetch!(path = "/", timeout = 5)
+    );
+
+    // Some fields specified
+    assert_eq!(
+        "path=[/], retries=[3], verbose=[false], timeout=[30]",
+        &fetch!(path = "/", retries = 3)
+    );
+    assert_eq!(
+        "path=[/], retries=[3], verbose=[true], timeout=[30]",
+        &fetch!(path = "/", retries = 3, verbose = true)
+    );
+    assert_eq!(
+        "path=[/], retries=[0], verbose=[true], timeout=[5]",
+        &fetch!(path = "/", verbose = true, timeout = 5)
+    );
+
+    // Fields can be interleaved with function arguments
+    assert_eq!(
+        "path=[/], retries=[3], verbose=[true], timeout=[5]",
+        &fetch!(retries = 3, path = "/", timeout = 5, verbose = true)
+    );
+
+    // Or the whole struct can be passed explicitly
+    assert_eq!(
+        "path=[/], retries=[1], verbose=[true], timeout=[30]",
+        &fetch!(
+            path = "/",
+            options = FetchOptions {
+                retries: 1,
+                verbose: true
+            }
+        )
+    );
+}
@@ -0,0 +1,36 @@
+use named::named;
+
+pub struct Style {
+    pub bold: bool,
+    pub size: u8,
+}
+
+impl Style {
+    const fn heading() -> Self {
+        Style {
+            bold: true,
+            size: 24,
+        }
+    }
+}
+
+#[named(options = Style, defaults(style = Style::heading()))]
+fn render(style: Style, text: &str) -> String {
+    format!(
+        "bold=[{}], size=[{}], text=[{}]",
+        style.bold, style.size, text
+    )
+}
+
+fn main() {
+    // The options struct's fields are filled in from its default value, rather than its Default impl.
+    assert_eq!("bold=[true], size=[24], text=[hi]", &render!(text = "hi"));
+    assert_eq!(
+        "bold=[true], size=[12], text=[hi]",
+        &render!(size = 12, text = "hi")
+    );
+    assert_eq!(
+        "bold=[false], size=[12], text=[hi]",
+        &render!(bold = false, size = 12, text = "hi",)
+    );
+}
@@ -0,0 +1,67 @@
+#![deny(warnings)]
+
+use named::{named, named_module, named_trait};
+
+#[named_module]
+mod config {
+    // Only one of these exists, and only its macros may be generated, or the other's would shadow them.
+    #[cfg(all())]
+    #[defaults(a = 1)]
+    pub fn pick(a: u8) -> u8 {
+        a
+    }
+
+    #[cfg(not(all()))]
+    #[defaults(b = 2)]
+    pub fn pick(b: u8) -> u8 {
+        b
+    }
+
+    #[cfg_attr(all(), cfg(not(all())))]
+    #[defaults(a = 1)]
+    pub fn never(a: u8) -> u8 {
+        a
+    }
+
+    pub fn pick_default() -> u8 {
+        pick!()
+    }
+}
+
+#[named_trait]
+trait Shape {
+    #[cfg(all())]
+    #[defaults(scale = 1)]
+    fn area(&self, scale: u32) -> u32;
+
+    #[cfg(not(all()))]
+    fn perimeter(&self) -> u32;
+}
+
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self, scale: u32) -> u32 {
+        self.0 * self.0 * scale
+    }
+}
+
+#[cfg(not(all()))]
+named::wrap!(fn repeat = str::repeat(s: &str, n: usize) -> String);
+
+#[cfg(all())]
+named::wrap!(fn repeat = str::repeat(s: &str, n: usize) -> String; defaults(n = 2));
+
+// `must_use` is kept on the function, so still applies through the macro.
+#[named(defaults(b = 1))]
+#[must_use]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn main() {
+    assert_eq!(config::pick_default(), 1);
+    assert_eq!(Shape_area!(self = &Square(2)), 4);
+    assert_eq!(repeat!(s = "a"), "aa");
+    let _ = add!(a = 1);
+}
@@ -0,0 +1,87 @@
+use named::named;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+#[named(defaults(b = 1))]
+const fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+const THREE: u8 = add!(a = 2);
+static FOUR: u8 = add!(a = 2, b = 2);
+const FIVE: [u8; add!(a = 4) as usize] = [0; 5];
+
+/// # Safety
+///
+/// `p` must be valid for reads.
+#[named(defaults(offset = 0))]
+unsafe fn read(p: *const u8, offset: usize) -> u8 {
+    *p.add(offset)
+}
+
+#[named(defaults(b = 1))]
+async fn async_add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[named(defaults(b = 1))]
+extern "C" fn c_add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[named(defaults(b = 1))]
+#[no_mangle]
+extern "C" fn named_exported_add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+extern "C" {
+    // Renaming the function for the macro must not change its symbol.
+    #[link_name = "named_exported_add"]
+    fn exported_add(a: u8, b: u8) -> u8;
+}
+
+#[named(defaults(b = 1))]
+const unsafe extern "C" fn all_qualifiers(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+const SIX: u8 = unsafe { all_qualifiers!(a = 5) };
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = Pin::as_mut(&mut future).poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(THREE, 3);
+    assert_eq!(FOUR, 4);
+    assert_eq!(FIVE.len(), 5);
+    assert_eq!(SIX, 6);
+
+    let bytes = [1, 2, 3];
+    assert_eq!(unsafe { read!(p = bytes.as_ptr()) }, 1);
+    assert_eq!(unsafe { read!(p = bytes.as_ptr(), offset = 2) }, 3);
+
+    assert_eq!(block_on(async { async_add!(a = 1).await }), 2);
+    assert_eq!(block_on(async_add!(a = 1, b = 2)), 3);
+
+    assert_eq!(c_add!(a = 1), 2);
+    assert_eq!(named_exported_add!(a = 2), 3);
+    assert_eq!(unsafe { exported_add(2, 2) }, 4);
+}
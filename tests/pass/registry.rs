@@ -0,0 +1,221 @@
+use named::{args, named, Args, CallError, Registry, Type, Value};
+use shapes::*;
+use std::any::Any;
+
+#[named(register, defaults(height = 1, fill = '#'))]
+fn resize(width: u32, height: u32, fill: char) -> String {
+    (0..height)
+        .map(|_| std::iter::repeat(fill).take(width as usize).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[named(register, defaults(greeting = "Hello"))]
+fn greet(name: String, greeting: &str, #[cfg(any())] volume: u8) -> String {
+    format!("{}, {}!", greeting, name)
+}
+
+#[macro_use]
+mod shapes {
+    use named::named;
+
+    #[named(register, macro_name = area)]
+    pub fn rectangle_area(width: f64, height: f64) -> f64 {
+        width * height
+    }
+}
+
+#[named(register)]
+fn reset() {}
+
+#[allow(unused_macros)]
+mod maintenance {
+    use named::named;
+
+    // Shares its name with another registered function, so needs calling by its path.
+    #[named(register, defaults(hard = false))]
+    pub fn reset(hard: bool) -> &'static str {
+        if hard {
+            "hard"
+        } else {
+            "soft"
+        }
+    }
+}
+
+/// A scripting language's values, which convert to and from the types of functions' parameters and results.
+#[derive(Debug, PartialEq)]
+enum Script {
+    Int(i64),
+    Text(String),
+    Unit,
+}
+
+impl Value for Script {
+    fn into_arg(self, ty: Type) -> Result<Box<dyn Any>, Self> {
+        match self {
+            Script::Int(i) if ty.is::<u32>() => Ok(Box::new(i as u32)),
+            Script::Int(i) if ty.is::<f64>() => Ok(Box::new(i as f64)),
+            Script::Text(s) if ty.is::<String>() => Ok(Box::new(s)),
+            Script::Text(s) if ty.is::<char>() && s.chars().count() == 1 => {
+                Ok(Box::new(s.chars().next().unwrap()))
+            }
+            other => Err(other),
+        }
+    }
+
+    fn from_any(value: Box<dyn Any>, ty: Type) -> Result<Self, Box<dyn Any>> {
+        if ty.is::<()>() {
+            return Ok(Script::Unit);
+        }
+        let value = match value.downcast::<String>() {
+            Ok(s) => return Ok(Script::Text(*s)),
+            Err(value) => value,
+        };
+        let value = match value.downcast::<&'static str>() {
+            Ok(s) => return Ok(Script::Text(s.to_string())),
+            Err(value) => value,
+        };
+        let value = match value.downcast::<f64>() {
+            Ok(f) => return Ok(Script::Int(*f as i64)),
+            Err(value) => value,
+        };
+        match value.downcast::<i32>() {
+            Ok(i) => Ok(Script::Int(i64::from(*i))),
+            Err(value) => Err(value),
+        }
+    }
+}
+
+/// Values which claim to convert to any type, but always convert to an `i64`.
+struct Careless(i64);
+
+impl Value for Careless {
+    fn into_arg(self, _ty: Type) -> Result<Box<dyn Any>, Self> {
+        Ok(Box::new(self.0))
+    }
+
+    fn from_any(value: Box<dyn Any>, _ty: Type) -> Result<Self, Box<dyn Any>> {
+        Err(value)
+    }
+}
+
+fn main() {
+    let registry = Registry::new();
+    let mut names: Vec<_> = registry.functions().map(|f| f.signature.name).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["area", "greet", "reset", "reset", "resize"]);
+
+    // Defaults fill in whatever isn't given, as they do when calling the functions' macros.
+    assert_eq!(
+        registry.call("resize", args! { width: 3 }),
+        Ok(Script::Text(resize!(width = 3)))
+    );
+    assert_eq!(
+        registry.call("resize", args! { fill: "*", height: 2, width: 2 }),
+        Ok(Script::Text("**\n**".to_owned()))
+    );
+    assert_eq!(
+        registry.call("area", args! { width: 3, height: 4 }),
+        Ok(Script::Int(area!(width = 3.0, height = 4.0) as i64))
+    );
+    let reset = format!("{}::reset", module_path!());
+    assert_eq!(registry.call(&reset, Args::new()), Ok(Script::Unit));
+    reset!();
+    let maintenance_reset = format!("{}::maintenance::reset", module_path!());
+    assert_eq!(
+        registry.call(&maintenance_reset, Args::new()),
+        Ok(Script::Text("soft".to_owned()))
+    );
+    assert_eq!(registry.get(&maintenance_reset).unwrap().path(), maintenance_reset);
+    assert_eq!(
+        registry.call::<Script>("reset", Args::new()),
+        Err(CallError::AmbiguousFunction {
+            name: "reset".to_owned(),
+            candidates: vec![maintenance_reset, reset],
+        })
+    );
+    assert!(registry.get("reset").is_none());
+    assert_eq!(
+        registry.call(
+            "greet",
+            Args::new().with_value("name", Script::Text("World".to_owned()))
+        ),
+        Ok(Script::Text(greet!(name = "World".to_owned())))
+    );
+
+    // Boxed values must be exactly the parameter's type.
+    let result = registry
+        .call::<Box<dyn Any>>("greet", args! { greeting: "Hi", name: "you".to_owned() })
+        .unwrap();
+    assert_eq!(result.downcast_ref::<String>().unwrap(), "Hi, you!");
+    assert_eq!(
+        registry
+            .call::<Box<dyn Any>>("resize", args! { width: 3_i64 })
+            .unwrap_err(),
+        CallError::WrongType {
+            function: "resize",
+            argument: "width",
+            expected: "u32",
+        }
+    );
+
+    // Calls are checked as calls of the functions' macros are at compile time.
+    let unknown = registry
+        .call::<Script>("resize", args! { width: 3, depth: 2 })
+        .unwrap_err();
+    assert_eq!(
+        unknown.to_string(),
+        "Unrecognized named argument to `resize` - got value for argument `depth` but only expected [width, height, fill]"
+    );
+    let missing = registry
+        .call::<Script>("area", args! { width: 3 })
+        .unwrap_err();
+    assert_eq!(
+        missing.to_string(),
+        "Must specify value for non-defaulted argument to `area`: `height`"
+    );
+    let duplicate = registry
+        .call::<Script>("area", args! { width: 3, height: 1, width: 4 })
+        .unwrap_err();
+    assert_eq!(
+        duplicate,
+        CallError::DuplicateArgument {
+            function: "area",
+            argument: "width",
+        }
+    );
+    let disabled = registry
+        .call::<Script>("greet", args! { name: "World".to_owned(), volume: 11 })
+        .unwrap_err();
+    assert!(matches!(disabled, CallError::UnknownArgument { .. }));
+    assert_eq!(
+        registry.call::<Script>("shrink", args! { width: 3 }),
+        Err(CallError::UnknownFunction("shrink".to_owned()))
+    );
+    assert_eq!(
+        registry.call::<Script>("resize", args! { width: 3.5_f32 }),
+        Err(CallError::UnsupportedArgument {
+            function: "resize",
+            argument: "width",
+            ty: "f32",
+        })
+    );
+
+    // Conversions which produce the wrong type are caught rather than trusted.
+    assert_eq!(
+        registry
+            .call("area", Args::new().with_value("width", Careless(3)).with_value("height", Careless(4)))
+            .err()
+            .unwrap(),
+        CallError::WrongType {
+            function: "area",
+            argument: "width",
+            expected: "f64",
+        }
+    );
+
+    let resize = registry.get("resize").unwrap();
+    assert_eq!(resize.signature.param("fill").unwrap().default, Some("'#'"));
+    assert!(resize.signature.param("width").unwrap().required);
+}
@@ -0,0 +1,57 @@
+use named::named;
+use std::collections::{BTreeSet, HashMap};
+
+#[named(
+    schema,
+    docs(
+        width = "How many columns to draw.",
+        fill = "The character to \"fill\" with."
+    ),
+    defaults(height = 1, fill = '#', scale = -0.5, label = "untitled", border = false)
+)]
+fn resize(
+    width: u32,
+    height: u32,
+    fill: char,
+    scale: f64,
+    label: &str,
+    border: bool,
+    offset: Option<(i32, i32)>,
+) {
+    let _ = (width, height, fill, scale, label, border, offset);
+}
+
+#[named(schema, defaults(tags = BTreeSet::new(), limits = HashMap::new()))]
+fn tag(
+    names: Vec<String>,
+    tags: BTreeSet<String>,
+    limits: HashMap<String, Option<u8>>,
+    corners: [f32; 4],
+    extra: Box<Option<std::path::PathBuf>>,
+    custom: std::time::Duration,
+    #[cfg(any())] disabled: bool,
+) {
+    let _ = (names, tags, limits, corners, extra, custom);
+}
+
+#[named(schema)]
+fn nothing() {}
+
+fn main() {
+    assert_eq!(resize_schema(), include_str!("../schema/resize.json").trim_end());
+    assert_eq!(tag_schema(), include_str!("../schema/tag.json").trim_end());
+    assert_eq!(nothing_schema(), include_str!("../schema/nothing.json").trim_end());
+
+    let schema: serde_json::Value = serde_json::from_str(resize_schema()).unwrap();
+    assert_eq!(schema["required"], serde_json::json!(["width"]));
+    assert_eq!(schema["properties"]["scale"]["default"], -0.5);
+
+    resize!(width = 1, offset = None);
+    tag!(
+        names = Vec::new(),
+        corners = [0.0; 4],
+        extra = Box::new(None),
+        custom = Default::default()
+    );
+    nothing!();
+}
@@ -0,0 +1,94 @@
+use named::{named, DeserializeError};
+use serde_json::json;
+
+#[named(serde, defaults(greeting = "Hello".to_owned(), times = 1))]
+fn greet(name: String, greeting: String, times: usize) -> String {
+    format!("{}, {}!", greeting, name).repeat(times)
+}
+
+#[named(serde, docs(input = "The text to parse."), defaults(fallback = T::default()))]
+fn parse_or<T: std::str::FromStr + Default>(input: &str, fallback: T) -> T {
+    input.parse().unwrap_or(fallback)
+}
+
+#[named(serde)]
+fn nothing() {}
+
+#[named(serde)]
+fn label(text: String, suffix: Option<char>) -> String {
+    format!("{}{}", text, suffix.map(String::from).unwrap_or_default())
+}
+
+#[named(serde, defaults(port = 80))]
+unsafe fn connect(host: &str, port: u16, #[cfg(any())] cert: &str) -> String {
+    format!("{}:{}", host, port)
+}
+
+fn main() {
+    assert_eq!(
+        greet_from_deserializer(json!({ "name": "World" })).unwrap(),
+        "Hello, World!"
+    );
+    assert_eq!(
+        greet_from_deserializer(json!({ "times": 2, "name": "you", "greeting": "Hi" })).unwrap(),
+        "Hi, you!Hi, you!"
+    );
+
+    let missing = greet_from_deserializer(json!({ "times": 2 })).unwrap_err();
+    assert!(matches!(missing, DeserializeError::MissingArgument { argument: "name", .. }));
+    assert_eq!(missing.argument(), Some("name"));
+    assert_eq!(missing.error().to_string(), "missing field `name`");
+    assert_eq!(
+        missing.to_string(),
+        "Must specify value for non-defaulted argument: `name`"
+    );
+    let unknown = greet_from_deserializer(json!({ "name": "World", "volume": 11 })).unwrap_err();
+    assert_eq!(unknown.argument(), Some("volume"));
+    assert_eq!(
+        unknown.to_string(),
+        "Unrecognized named argument - got value for argument `volume` but only expected [name, greeting, times]"
+    );
+    let invalid = greet_from_deserializer(json!({ "name": "World", "times": "twice" })).unwrap_err();
+    assert!(matches!(invalid, DeserializeError::InvalidArgument { argument: "times", .. }));
+    assert!(invalid
+        .to_string()
+        .starts_with("Invalid value for argument `times`: invalid type: string \"twice\""));
+    let mut de = serde_json::Deserializer::from_str(r#"{ "name": "a", "name": "b" }"#);
+    let duplicate = greet_from_deserializer(&mut de).unwrap_err();
+    assert!(matches!(duplicate, DeserializeError::DuplicateArgument { argument: "name", .. }));
+    let malformed = greet_from_deserializer(json!(["World"])).unwrap_err();
+    assert!(matches!(malformed, DeserializeError::Malformed(_)));
+    assert_eq!(malformed.argument(), None);
+
+    let args: GreetArgs = serde_json::from_str(r#"{ "name": "args" }"#).unwrap();
+    assert_eq!(args.greeting, "Hello");
+    assert_eq!(args.call(), "Hello, args!");
+
+    let mut de = serde_json::Deserializer::from_str(r#"{ "input": "12" }"#);
+    assert_eq!(parse_or_from_deserializer::<u8, _>(&mut de).unwrap(), 12);
+    let args: ParseOrArgs<i32> = serde_json::from_str(r#"{ "input": "x", "fallback": -1 }"#).unwrap();
+    assert_eq!(args.input, "x");
+    assert_eq!(args.call(), -1);
+
+    nothing_from_deserializer(json!({})).unwrap();
+
+    // As with derived implementations, absent `Option` arguments are `None`.
+    assert_eq!(label_from_deserializer(json!({ "text": "a" })).unwrap(), "a");
+    assert_eq!(label_from_deserializer(json!({ "text": "a", "suffix": "!" })).unwrap(), "a!");
+    let missing = label_from_deserializer(json!({ "suffix": "!" })).unwrap_err();
+    assert_eq!(missing.argument(), Some("text"));
+
+    // Borrowed arguments need a deserializer which can lend them.
+    let mut de = serde_json::Deserializer::from_str(r#"{ "host": "localhost" }"#);
+    let connected = unsafe { connect_from_deserializer(&mut de) };
+    assert_eq!(connected.unwrap(), "localhost:80");
+    let mut de = serde_json::Deserializer::from_str(r#"{ "host": "localhost", "cert": "x" }"#);
+    let unknown = unsafe { connect_from_deserializer(&mut de) };
+    assert_eq!(unknown.unwrap_err().argument(), Some("cert"));
+
+    assert_eq!(greet!(name = "macro".to_owned()), "Hello, macro!");
+    assert_eq!(parse_or!(<T = u8>, input = "7"), 7);
+    nothing!();
+    assert_eq!(label!(text = "macro".to_owned(), suffix = None), "macro");
+    assert_eq!(unsafe { connect!(host = "macro") }, "macro:80");
+}
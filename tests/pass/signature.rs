@@ -0,0 +1,100 @@
+use named::{named, named_trait, Param, Signature};
+
+#[named(defaults(b = 2 * 3, c = vec![1, 2]))]
+fn sum(a: u8, b: u8, c: Vec<u8>) -> u8 {
+    a + b + c.iter().sum::<u8>()
+}
+
+#[named(macro_name = greet, defaults(name = "world"))]
+fn greet_impl(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[named(defaults(separator = "a , b ( c"))]
+fn split(text: &str, separator: &str) -> usize {
+    text.split(separator).count()
+}
+
+#[named_trait]
+trait Counter {
+    #[defaults(by = 1)]
+    fn bump(&mut self, by: u32);
+}
+
+#[macro_use]
+mod shapes {
+    use named::named;
+
+    #[named(defaults(sides = 4))]
+    pub fn polygon(sides: u8) -> u8 {
+        sides
+    }
+}
+
+struct Tally(u32);
+
+impl Counter for Tally {
+    fn bump(&mut self, by: u32) {
+        self.0 += by;
+    }
+}
+
+use shapes::*;
+
+const SUM: Signature = named::signature!(sum);
+
+fn main() {
+    // Literals are reported exactly as they're written, however they're spaced.
+    assert_eq!(
+        named::signature!(split).param("separator").unwrap().default,
+        Some("\"a , b ( c\"")
+    );
+    assert_eq!(split!(text = "xa , b ( cy"), 2);
+
+    assert_eq!(
+        SUM,
+        Signature {
+            name: "sum",
+            params: &[
+                Param {
+                    name: "a",
+                    ty: "u8",
+                    required: true,
+                    default: None,
+                },
+                Param {
+                    name: "b",
+                    ty: "u8",
+                    required: false,
+                    default: Some("2 * 3"),
+                },
+                Param {
+                    name: "c",
+                    ty: "Vec<u8>",
+                    required: false,
+                    default: Some("vec![1, 2]"),
+                },
+            ],
+        }
+    );
+    assert_eq!(SUM.param("c").map(|param| param.ty), Some("Vec<u8>"));
+    assert_eq!(SUM.param("d"), None);
+
+    let greet = named::signature!(greet);
+    assert_eq!(greet.name, "greet");
+    assert_eq!(greet.params[0].ty, "&str");
+    assert_eq!(greet.params[0].default, Some("\"world\""));
+
+    let bump = named::signature!(Counter_bump);
+    let names: Vec<_> = bump.params.iter().map(|param| param.name).collect();
+    assert_eq!(names, ["self", "by"]);
+    assert!(bump.params[0].required);
+    let mut tally = Tally(0);
+    Counter_bump!(self = &mut tally);
+    assert_eq!(tally.0, 1);
+
+    assert_eq!(named::signature!(polygon).params[0].default, Some("4"));
+    assert_eq!(sum!(a = 1), 10);
+    assert_eq!(greet!(), "Hello, world!");
+    assert_eq!(polygon!(), 4);
+}
@@ -0,0 +1,64 @@
+use named::named;
+
+pub struct Request {
+    pub url: &'static str,
+    pub retries: u8,
+    pub timeout: u32,
+}
+
+#[named(defaults(retries = 0, timeout = 30))]
+fn fetch(url: &str, retries: u8, timeout: u32) -> String {
+    format!("url=[{}], retries=[{}], timeout=[{}]", url, retries, timeout)
+}
+
+fn base() -> Request {
+    Request {
+        url: "/base",
+        retries: 5,
+        timeout: 60,
+    }
+}
+
+fn main() {
+    // Everything from the spread struct, including values which would otherwise be required.
+    assert_eq!("url=[/base], retries=[5], timeout=[60]", &fetch!(..base()));
+
+    // Leading spread
+    assert_eq!(
+        "url=[/base], retries=[5], timeout=[1]",
+        &fetch!(..base(), timeout = 1)
+    );
+    assert_eq!(
+        "url=[/], retries=[5], timeout=[1]",
+        &fetch!(..base(), url = "/", timeout = 1)
+    );
+
+    // Trailing spread
+    assert_eq!(
+        "url=[/base], retries=[2], timeout=[60]",
+        &fetch!(retries = 2, ..base())
+    );
+    assert_eq!(
+        "url=[/], retries=[2], timeout=[3]",
+        &fetch!(url = "/", retries = 2, timeout = 3, ..base())
+    );
+
+    // Any struct whose fields match the argument names can be spread.
+    struct Partial {
+        url: &'static str,
+        retries: u8,
+        timeout: u32,
+        #[allow(dead_code)]
+        unrelated: bool,
+    }
+    let partial = Partial {
+        url: "/partial",
+        retries: 7,
+        timeout: 8,
+        unrelated: true,
+    };
+    assert_eq!(
+        "url=[/partial], retries=[7], timeout=[9]",
+        &fetch!(..partial, timeout = 9)
+    );
+}
@@ -0,0 +1,52 @@
+use named::named;
+
+#[derive(Default)]
+pub struct FetchOptions {
+    pub retries: u8,
+    pub verbose: bool,
+}
+
+pub struct Base {
+    pub path: &'static str,
+    pub options: FetchOptions,
+}
+
+#[named(options = FetchOptions)]
+fn fetch(path: &str, options: FetchOptions) -> String {
+    format!(
+        "path=[{}], retries=[{}], verbose=[{}]",
+        path, options.retries, options.verbose
+    )
+}
+
+fn base() -> Base {
+    Base {
+        path: "/base",
+        options: FetchOptions {
+            retries: 5,
+            verbose: true,
+        },
+    }
+}
+
+fn main() {
+    assert_eq!("path=[/base], retries=[5], verbose=[true]", &fetch!(..base()));
+    assert_eq!(
+        "path=[/], retries=[5], verbose=[true]",
+        &fetch!(..base(), path = "/")
+    );
+
+    // Unspecified fields of the options struct come from the spread struct's options.
+    assert_eq!(
+        "path=[/base], retries=[1], verbose=[true]",
+        &fetch!(..base(), retries = 1)
+    );
+    assert_eq!(
+        "path=[/], retries=[1], verbose=[true]",
+        &fetch!(retries = 1, path = "/", ..base())
+    );
+    assert_eq!(
+        "path=[/base], retries=[0], verbose=[false]",
+        &fetch!(..base(), options = FetchOptions::default())
+    );
+}
@@ -0,0 +1,201 @@
+use named::named;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[named(trace, defaults(greeting = "Hello", times = 1))]
+fn greet(name: &str, greeting: &str, times: usize) -> String {
+    format!("{}, {}!", greeting, name).repeat(times)
+}
+
+struct Secret;
+
+#[named(trace, defaults(secret = Secret))]
+fn reveal(secret: Secret, hint: Option<u8>) -> u8 {
+    let Secret = secret;
+    hint.unwrap_or(0)
+}
+
+#[named(trace, defaults(fallback = T::default()))]
+fn parse_or<T: std::str::FromStr + Default>(input: &str, fallback: T) -> T {
+    input.parse().unwrap_or(fallback)
+}
+
+#[named(trace, defaults(port = 80))]
+async unsafe fn connect(host: &str, port: u16, #[cfg(any())] cert: &str) -> String {
+    format!("{}:{}", host, port)
+}
+
+/// Logs, as recorded by the `log` feature.
+static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        LOGS.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+/// Spans, as recorded by the `tracing` feature, with their fields.
+#[derive(Clone, Default)]
+struct Spans {
+    spans: Arc<Mutex<Vec<(&'static str, BTreeMap<String, String>)>>>,
+}
+
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_owned(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = BTreeMap::new();
+        span.record(&mut Fields(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields));
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let (_name, fields) = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut Fields(fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+impl Spans {
+    fn take(&self) -> Vec<(&'static str, Vec<(String, String)>)> {
+        self.spans
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|(name, fields)| (name, fields.into_iter().collect()))
+            .collect()
+    }
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn take_logs() -> Vec<String> {
+    LOGS.lock().unwrap().drain(..).collect()
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("Future wasn't ready"),
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    let spans = Spans::default();
+    let _default = tracing::subscriber::set_default(spans.clone());
+
+    assert_eq!(greet!(name = "World", times = 2), "Hello, World!Hello, World!");
+    assert_eq!(
+        take_logs(),
+        [r#"greet(name = "World", greeting = "Hello" (defaulted), times = 2)"#]
+    );
+    assert_eq!(
+        spans.take(),
+        [(
+            "greet",
+            fields(&[
+                ("greeting", r#""Hello""#),
+                ("greeting.defaulted", "true"),
+                ("name", r#""World""#),
+                ("times", "2"),
+            ])
+        )]
+    );
+
+    // Calls through `named::call!` are recorded too.
+    assert_eq!(named::call!(greet(greeting = "Hi", name = "you")), "Hi, you!");
+    assert_eq!(
+        take_logs(),
+        [r#"greet(name = "you", greeting = "Hi", times = 1 (defaulted))"#]
+    );
+    spans.take();
+
+    // Arguments which don't implement `Debug` are recorded as `_`.
+    assert_eq!(reveal!(hint = Some(4)), 4);
+    assert_eq!(
+        take_logs(),
+        ["reveal(secret = _ (defaulted), hint = Some(4))"]
+    );
+    assert_eq!(
+        spans.take(),
+        [(
+            "reveal",
+            fields(&[
+                ("hint", "Some(4)"),
+                ("secret", "_"),
+                ("secret.defaulted", "true"),
+            ])
+        )]
+    );
+
+    assert_eq!(parse_or!(input = "x", fallback = 7_u8), 7);
+    assert_eq!(parse_or!(<T = i32>, input = "12"), 12);
+    assert_eq!(
+        take_logs(),
+        [
+            r#"parse_or(input = "x", fallback = _)"#,
+            r#"parse_or(input = "12", fallback = _ (defaulted))"#,
+        ]
+    );
+    spans.take();
+
+    let connected = block_on(unsafe { connect!(host = "localhost") });
+    assert_eq!(connected, "localhost:80");
+    assert_eq!(
+        take_logs(),
+        [r#"connect(host = "localhost", port = 80 (defaulted))"#]
+    );
+    assert_eq!(
+        spans.take(),
+        [(
+            "connect",
+            fields(&[
+                ("host", r#""localhost""#),
+                ("port", "80"),
+                ("port.defaulted", "true"),
+            ])
+        )]
+    );
+}
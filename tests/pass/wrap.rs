@@ -0,0 +1,20 @@
+named::wrap!(fn clamp = core::cmp::Ord::clamp(value: T, min: T, max: T); defaults(min = 0, max = 10));
+named::wrap!(fn repeat = str::repeat(s: &str, n: usize) -> String; defaults(n = 2));
+named::wrap!(fn pair = make_pair(left: u8, right: u8));
+
+fn make_pair(left: u8, right: u8) -> (u8, u8) {
+    (left, right)
+}
+
+fn main() {
+    assert_eq!(clamp!(value = 7), 7);
+    assert_eq!(clamp!(value = 17), 10);
+    assert_eq!(clamp!(value = 7, max = 5), 5);
+    assert_eq!(clamp!(value = -2, max = 5), 0);
+    assert_eq!(clamp!(value = -2, min = -1, max = 5), -1);
+
+    assert_eq!(repeat!(s = "ab"), "abab");
+    assert_eq!(repeat!(s = "ab", n = 3), "ababab");
+
+    assert_eq!(pair!(left = 1, right = 2), (1, 2));
+}